@@ -0,0 +1,199 @@
+use crate::cli::Format;
+use crate::input::InputSource;
+use crate::runner::parse_failure;
+use anyhow::Context;
+use aoc_2022::solver::Puzzle;
+use serde::{Serialize, Serializer};
+use std::time::{Duration, Instant};
+
+fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Statistics {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "as_nanos")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_ns", serialize_with = "as_nanos")]
+    pub stddev: Duration,
+}
+
+impl Statistics {
+    pub(crate) fn from_samples(samples: &[Duration]) -> Option<Statistics> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Statistics {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Stage {
+    Parse,
+    Solve,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Benchmark {
+    pub day: usize,
+    pub challenge: Option<usize>, // none when timing the parser
+    pub stage: Stage,
+    pub iterations: usize,
+    #[serde(flatten)]
+    pub statistics: Statistics,
+}
+
+fn time<T>(
+    mut f: impl FnMut() -> anyhow::Result<T>,
+    iterations: usize,
+) -> anyhow::Result<Vec<Duration>> {
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+// the input is read once, parsing and each challenge are timed separately
+pub(crate) fn bench_day(
+    puzzle: &dyn Puzzle,
+    day: usize,
+    challenges: &[usize],
+    input_source: &InputSource,
+    iterations: usize,
+) -> anyhow::Result<Vec<Benchmark>> {
+    let iterations = iterations.max(1);
+    let input = input_source.read_to_string()?;
+    let mut benchmarks = vec![];
+
+    let samples = time(|| puzzle.parse(input.as_str()), iterations)
+        .map_err(|e| parse_failure(input_source, &e))?;
+    benchmarks.push(Benchmark {
+        day,
+        challenge: None,
+        stage: Stage::Parse,
+        iterations,
+        statistics: Statistics::from_samples(&samples).expect("at least one iteration"),
+    });
+
+    let parsed = puzzle.parse(input.as_str())?;
+    for &challenge in challenges {
+        let samples = time(|| parsed.solve(challenge), iterations)
+            .with_context(|| format!("day {} challenge {} failed", day, challenge))?;
+        benchmarks.push(Benchmark {
+            day,
+            challenge: Some(challenge),
+            stage: Stage::Solve,
+            iterations,
+            statistics: Statistics::from_samples(&samples).expect("at least one iteration"),
+        });
+    }
+    Ok(benchmarks)
+}
+
+fn stage_name(stage: Stage) -> &'static str {
+    match stage {
+        Stage::Parse => "parse",
+        Stage::Solve => "solve",
+    }
+}
+
+fn print_table(benchmarks: &[Benchmark]) {
+    println!(
+        "{:>3}  {:>9}  {:<5}  {:>10}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "day", "challenge", "stage", "iterations", "min", "median", "mean", "stddev"
+    );
+    for benchmark in benchmarks {
+        let challenge = benchmark
+            .challenge
+            .map_or(String::from("-"), |challenge| challenge.to_string());
+        let stage = stage_name(benchmark.stage);
+        let statistics = &benchmark.statistics;
+        println!(
+            "{:>3}  {:>9}  {:<5}  {:>10}  {:>12.2?}  {:>12.2?}  {:>12.2?}  {:>12.2?}",
+            benchmark.day,
+            challenge,
+            stage,
+            benchmark.iterations,
+            statistics.min,
+            statistics.median,
+            statistics.mean,
+            statistics.stddev
+        );
+    }
+}
+
+pub(crate) fn print_benchmarks(benchmarks: &[Benchmark], format: Format) -> anyhow::Result<()> {
+    match format {
+        Format::Text => print_table(benchmarks),
+        Format::Json => println!("{}", serde_json::to_string_pretty(benchmarks)?),
+        Format::Csv => {
+            println!("day,challenge,stage,iterations,min_ns,median_ns,mean_ns,stddev_ns");
+            for benchmark in benchmarks {
+                let statistics = &benchmark.statistics;
+                println!(
+                    "{},{},{},{},{},{},{},{}",
+                    benchmark.day,
+                    benchmark
+                        .challenge
+                        .map_or(String::new(), |challenge| challenge.to_string()),
+                    stage_name(benchmark.stage),
+                    benchmark.iterations,
+                    statistics.min.as_nanos(),
+                    statistics.median.as_nanos(),
+                    statistics.mean.as_nanos(),
+                    statistics.stddev.as_nanos()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::Statistics;
+    use std::time::Duration;
+
+    #[test]
+    fn test_statistics_from_samples() {
+        assert_eq!(Statistics::from_samples(&[]), None);
+        let statistics = Statistics::from_samples(&[
+            Duration::from_millis(4),
+            Duration::from_millis(2),
+            Duration::from_millis(8),
+            Duration::from_millis(6),
+        ])
+        .unwrap();
+        assert_eq!(statistics.min, Duration::from_millis(2));
+        assert_eq!(statistics.median, Duration::from_millis(5));
+        assert_eq!(statistics.mean, Duration::from_millis(5));
+        assert_eq!(statistics.stddev.as_micros(), 2236); // sqrt(5) ms
+    }
+}
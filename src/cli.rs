@@ -1,13 +1,59 @@
-use clap::Parser;
+use crate::input::InputSource;
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub(crate) struct Args {
-    #[arg(short, long)]
-    pub day: usize,
-    #[arg(short, long)]
-    pub challenge: usize,
-    #[arg(long, default_value_os_t = PathBuf::from("data"))]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Day to solve, every available day when omitted
+    #[arg(short, long, global = true)]
+    pub day: Option<usize>,
+    /// Challenge to solve, both challenges when omitted
+    #[arg(short, long, global = true)]
+    pub challenge: Option<usize>,
+    #[arg(long, global = true, default_value_os_t = PathBuf::from("data"))]
     pub data_path: PathBuf,
+    /// Puzzle input file, `-` for stdin, NN.txt in the data path by default
+    #[arg(short, long, global = true)]
+    pub input: Option<InputSource>,
+    /// Output format for answers and benchmarks
+    #[arg(short, long, global = true, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// Compare answers with the expected answers manifest
+    Verify {
+        /// Expected answers manifest, answers.toml in the data path by default
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Time parsing and solving over several iterations
+    Bench {
+        /// Timed iterations for each day and challenge
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+    },
+    /// Download puzzle inputs to NN.txt in the data path, cached inputs are never downloaded again
+    Fetch {
+        /// Configuration file with `session` and `base_url`, ~/.config/aoc_2022/config.toml by default
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Advent of Code URL, overrides the configuration file
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Minimum seconds between two requests
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
 }
@@ -0,0 +1,302 @@
+use anyhow::{anyhow, bail, Context};
+use aoc_2022::solver::input_file_name;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+pub(crate) const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+pub(crate) const SESSION_ENV_VAR: &str = "AOC_SESSION";
+const USER_AGENT: &str = concat!("aoc_2022/", env!("CARGO_PKG_VERSION"));
+
+// settings read from the configuration file, all optional
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+pub(crate) struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+// $XDG_CONFIG_HOME/aoc_2022/config.toml, falling back to ~/.config/aoc_2022/config.toml
+pub(crate) fn default_config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config_home| config_home.join("aoc_2022").join("config.toml"))
+}
+
+// a missing configuration file is the same as an empty one
+pub(crate) fn load_config(path: &Path) -> anyhow::Result<Config> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read config {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("invalid config {}", path.display()))
+}
+
+// the environment variable takes precedence over the configuration file
+pub(crate) fn session_token(from_env: Option<String>, config: &Config) -> anyhow::Result<String> {
+    from_env
+        .or_else(|| config.session.clone())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "no session token, set {} or `session` in the config file",
+                SESSION_ENV_VAR
+            )
+        })
+}
+
+// where requests go, so that tests can swap the network for something else
+pub(crate) trait HttpClient {
+    // body of a successful GET request authenticated with the session cookie
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<String>;
+}
+
+pub(crate) struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub(crate) fn new() -> UreqClient {
+        UreqClient {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<String> {
+        match self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+        {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => {
+                bail!("{} answered {} {}", url, status, response.status_text())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// consecutive requests are at least interval apart
+struct RateLimiter {
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn wait(&mut self) {
+        if let Some(last_request) = self.last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.interval {
+                sleep(self.interval - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Fetched {
+    Downloaded(PathBuf),
+    Cached(PathBuf), // nothing was requested
+}
+
+// downloads puzzle inputs to NN.txt in the data path, each input is downloaded at most once
+pub(crate) struct Fetcher<C: HttpClient> {
+    client: C,
+    base_url: String,
+    session: String,
+    data_path: PathBuf,
+    limiter: RateLimiter,
+}
+
+impl<C: HttpClient> Fetcher<C> {
+    pub(crate) fn new(
+        client: C,
+        base_url: &str,
+        session: &str,
+        data_path: &Path,
+        interval: Duration,
+    ) -> Fetcher<C> {
+        Fetcher {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            data_path: data_path.to_path_buf(),
+            limiter: RateLimiter {
+                interval,
+                last_request: None,
+            },
+        }
+    }
+
+    pub(crate) fn fetch(&mut self, day: usize) -> anyhow::Result<Fetched> {
+        let path = self.data_path.join(input_file_name(day));
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        self.limiter.wait();
+        let url = format!("{}/day/{}/input", self.base_url, day);
+        let input = self
+            .client
+            .get(&url, &self.session)
+            .with_context(|| format!("failed to download the input of day {}", day))?;
+        fs::create_dir_all(&self.data_path)?;
+        fs::write(&path, input)?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fetch::{session_token, Config, Fetched, Fetcher, HttpClient, UreqClient};
+    use std::cell::RefCell;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // answers every request with the requested url
+    #[derive(Default)]
+    struct FakeClient {
+        requests: RefCell<Vec<String>>,
+    }
+
+    impl HttpClient for &FakeClient {
+        fn get(&self, url: &str, _session: &str) -> anyhow::Result<String> {
+            self.requests.borrow_mut().push(url.to_string());
+            Ok(format!("{}\n", url))
+        }
+    }
+
+    fn data_path(test: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("aoc_2022_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    #[test]
+    fn test_session_token() {
+        let config = Config {
+            session: Some("from config\n".to_string()),
+            base_url: None,
+        };
+        assert_eq!(
+            session_token(Some("from env".to_string()), &config).unwrap(),
+            "from env"
+        );
+        assert_eq!(session_token(None, &config).unwrap(), "from config");
+        assert!(session_token(None, &Config::default()).is_err());
+        assert_eq!(
+            toml::from_str::<Config>("session = \"abc\"\nbase_url = \"http://localhost\"\n")
+                .unwrap(),
+            Config {
+                session: Some("abc".to_string()),
+                base_url: Some("http://localhost".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_fetch_once_and_rate_limit() {
+        let data_path = data_path("fetch_once");
+        let client = FakeClient::default();
+        let interval = Duration::from_millis(50);
+        let mut fetcher = Fetcher::new(&client, "http://aoc.test/", "token", &data_path, interval);
+        let start = Instant::now();
+        let path = data_path.join("03.txt");
+        assert_eq!(fetcher.fetch(3).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(
+            fetcher.fetch(4).unwrap(),
+            Fetched::Downloaded(data_path.join("04.txt"))
+        );
+        assert!(start.elapsed() >= interval);
+        assert_eq!(fetcher.fetch(3).unwrap(), Fetched::Cached(path.clone()));
+        assert_eq!(
+            *client.requests.borrow(),
+            vec!["http://aoc.test/day/3/input", "http://aoc.test/day/4/input"]
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "http://aoc.test/day/3/input\n"
+        );
+        fs::remove_dir_all(&data_path).unwrap();
+    }
+
+    // a single response from a local server standing in for the real one
+    fn mock_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map(|line| line.unwrap())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_ureq_client_against_mock_server() {
+        let data_path = data_path("mock_server");
+        let (base_url, server) = mock_server("200 OK", "1000\n2000\n");
+        let mut fetcher = Fetcher::new(
+            UreqClient::new(),
+            &base_url,
+            "token",
+            &data_path,
+            Duration::ZERO,
+        );
+        assert!(matches!(fetcher.fetch(1).unwrap(), Fetched::Downloaded(_)));
+        assert_eq!(
+            fs::read_to_string(data_path.join("01.txt")).unwrap(),
+            "1000\n2000\n"
+        );
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /day/1/input HTTP/1.1");
+        assert!(request
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case("cookie: session=token")));
+
+        let (base_url, server) = mock_server("404 Not Found", "not yet unlocked");
+        let mut fetcher = Fetcher::new(
+            UreqClient::new(),
+            &base_url,
+            "token",
+            &data_path,
+            Duration::ZERO,
+        );
+        let error = fetcher.fetch(2).unwrap_err();
+        assert!(format!("{:#}", error).contains("404"));
+        assert!(!data_path.join("02.txt").exists());
+        server.join().unwrap();
+        fs::remove_dir_all(&data_path).unwrap();
+    }
+}
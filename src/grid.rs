@@ -0,0 +1,277 @@
+use anyhow::bail;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+
+    // none when the offset would lead to a negative row or column
+    pub fn offset(&self, drow: isize, dcol: isize) -> Option<Pos> {
+        Some(Pos {
+            row: self.row.checked_add_signed(drow)?,
+            col: self.col.checked_add_signed(dcol)?,
+        })
+    }
+
+    pub fn manhattan_distance(&self, other: &Pos) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+pub const CARDINALS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+impl Direction {
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turn_left().turn_left()
+    }
+}
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// row major storage, rows are numbered top to bottom
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    nrows: usize,
+    ncols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(nrows: usize, ncols: usize, value: T) -> Grid<T> {
+        Grid {
+            cells: vec![value; nrows * ncols],
+            nrows,
+            ncols,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Grid<T>> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, |row| row.len());
+        let mut cells = Vec::with_capacity(nrows * ncols);
+        for (r, row) in rows.into_iter().enumerate() {
+            if row.len() != ncols {
+                bail!("row {} has {} columns, expected {}", r, row.len(), ncols);
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            nrows,
+            ncols,
+        })
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.nrows && pos.col < self.ncols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            self.cells.get(pos.row * self.ncols + pos.col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            self.cells.get_mut(pos.row * self.ncols + pos.col)
+        } else {
+            None
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let ncols = self.ncols;
+        (0..self.nrows).flat_map(move |row| (0..ncols).map(move |col| Pos { row, col }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.ncols.max(1))
+    }
+
+    // none when the offset position is outside of the grid
+    pub fn offset(&self, pos: Pos, drow: isize, dcol: isize) -> Option<Pos> {
+        pos.offset(drow, dcol).filter(|p| self.contains(*p))
+    }
+
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        let (drow, dcol) = direction.delta();
+        self.offset(pos, drow, dcol)
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |(drow, dcol)| self.offset(pos, *drow, *dcol))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |(drow, dcol)| self.offset(pos, *drow, *dcol))
+    }
+
+    // positions from pos (excluded) to the edge of the grid
+    pub fn ray(&self, pos: Pos, direction: Direction) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, direction), move |p| self.step(*p, direction))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).expect("position inside the grid")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos).expect("position inside the grid")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (r, row) in self.rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Direction, Grid, Pos};
+    use crate::parser_utils::parse_grid;
+
+    fn example() -> Grid<char> {
+        parse_grid("abc\ndef\nghi\n", "a letter", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.nrows(), grid.ncols()), (3, 3));
+        assert_eq!(grid[Pos::new(1, 2)], 'f');
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert!(parse_grid("ab\nc\n", "a letter", Some).is_err());
+        assert!(Grid::from_rows(vec![vec!['a', 'b'], vec!['c']]).is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbours4(Pos::new(0, 0)).collect::<Vec<Pos>>(),
+            vec![Pos::new(1, 0), Pos::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(Pos::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Pos::new(0, 2)).count(), 3);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = example();
+        assert_eq!(
+            grid.ray(Pos::new(2, 1), Direction::Up)
+                .map(|p| grid[p])
+                .collect::<String>(),
+            "eb"
+        );
+        assert_eq!(grid.ray(Pos::new(1, 2), Direction::Right).count(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(example().to_string(), "abc\ndef\nghi");
+    }
+}
@@ -0,0 +1,46 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{stdin, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub(crate) fn read_to_string(&self) -> anyhow::Result<String> {
+        match self {
+            InputSource::File(input_path) => Ok(fs::read_to_string(input_path)?),
+            InputSource::Stdin => {
+                let mut buffer = String::new();
+                stdin().read_to_string(&mut buffer)?;
+                Ok(buffer)
+            }
+        }
+    }
+}
+
+// `-` reads from stdin, anything else is a file path
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(input_path) => write!(f, "{}", input_path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Advent of Code 2022 solutions, one module per day, plus the registry used by the CLI.
+
+pub mod grid;
+pub mod math_utils;
+pub mod parser_utils;
+pub mod solve_01;
+pub mod solve_02;
+pub mod solve_03;
+pub mod solve_04;
+pub mod solve_05;
+pub mod solve_06;
+pub mod solve_07;
+pub mod solve_08;
+pub mod solve_09;
+pub mod solve_10;
+pub mod solve_11;
+pub mod solve_12;
+pub mod solve_13;
+pub mod solve_14;
+pub mod solve_15;
+pub mod solve_16;
+pub mod solve_17;
+pub mod solve_18;
+pub mod solve_19;
+pub mod solve_20;
+pub mod solve_21;
+pub mod solve_22;
+pub mod solve_23;
+pub mod solve_24;
+pub mod solve_25;
+pub mod solver;
+
+use crate::solver::Registry;
+
+// every available day
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    registry.register(1, solve_01::CalorieCounting);
+    registry.register(2, solve_02::RockPaperScissors);
+    registry.register(3, solve_03::RucksackReorganization);
+    registry.register(4, solve_04::CampCleanup);
+    registry.register(5, solve_05::SupplyStacks);
+    registry.register(6, solve_06::TuningTrouble);
+    registry.register(7, solve_07::NoSpaceLeftOnDevice);
+    registry.register(8, solve_08::TreetopTreeHouse);
+    registry.register(9, solve_09::RopeBridge);
+    registry.register(10, solve_10::CathodeRayTube);
+    registry.register(11, solve_11::MonkeyInTheMiddle);
+    registry.register(12, solve_12::HillClimbingAlgorithm);
+    registry.register(13, solve_13::DistressSignal);
+    registry.register(14, solve_14::RegolithReservoir);
+    registry.register(15, solve_15::BeaconExclusionZone::default());
+    registry.register(16, solve_16::ProboscideaVolcanium);
+    registry.register(17, solve_17::PyroclasticFlow);
+    registry.register(18, solve_18::BoilingBoulders);
+    registry.register(19, solve_19::NotEnoughMinerals);
+    registry.register(20, solve_20::GrovePositioningSystem);
+    registry.register(21, solve_21::MonkeyMath);
+    registry.register(22, solve_22::MonkeyMap);
+    registry.register(23, solve_23::UnstableDiffusion);
+    registry.register(24, solve_24::BlizzardBasin);
+    registry.register(25, solve_25::FullOfHotAir);
+    registry
+}
@@ -1,245 +1,193 @@
 extern crate core;
 
+mod bench;
 mod cli;
-mod parser_utils;
-mod solve_01;
-mod solve_02;
-mod solve_03;
-mod solve_04;
-mod solve_05;
-mod solve_06;
-mod solve_07;
-mod solve_08;
-mod solve_09;
-mod solve_10;
-mod solve_11;
-mod solve_12;
-mod solve_14;
+mod fetch;
+mod input;
+mod runner;
+mod verify;
 
-use crate::cli::Args;
+use crate::bench::{bench_day, print_benchmarks, Benchmark};
+use crate::cli::{Args, Command, Format};
+use crate::fetch::{Fetched, Fetcher, UreqClient};
+use crate::input::InputSource;
+use crate::runner::{parse_failure, print_runs, run_day, Run};
+use crate::verify::Expected;
+use anyhow::{anyhow, bail};
+use aoc_2022::registry;
+use aoc_2022::solver::{input_file_name, Registry, CHALLENGES};
 use clap::Parser;
+use log::warn;
+use std::path::Path;
+use std::time::Duration;
+
+fn default_input_source(data_path: &Path, day: usize) -> InputSource {
+    InputSource::File(data_path.join(input_file_name(day)))
+}
+
+// days to run along with their input, an explicit input only makes sense for a single day
+fn select_days(
+    registry: &Registry,
+    day: Option<usize>,
+    data_path: &Path,
+    input: Option<InputSource>,
+) -> anyhow::Result<Vec<(usize, InputSource)>> {
+    match (day, input) {
+        (Some(day), input) => {
+            if registry.get(day).is_none() {
+                bail!("no solver available for day {}", day);
+            }
+            let input_source = input.unwrap_or_else(|| default_input_source(data_path, day));
+            Ok(vec![(day, input_source)])
+        }
+        (None, Some(_)) => bail!("--input needs a --day"),
+        // days without a puzzle input in the data path are skipped
+        (None, None) => Ok(registry
+            .days()
+            .filter(|day| {
+                let exists = data_path.join(input_file_name(*day)).exists();
+                if !exists {
+                    warn!("skipping day {}: no input in {}", day, data_path.display());
+                }
+                exists
+            })
+            .map(|day| (day, default_input_source(data_path, day)))
+            .collect()),
+    }
+}
+
+fn select_challenges(challenge: Option<usize>) -> Vec<usize> {
+    match challenge {
+        Some(challenge) => vec![challenge],
+        None => CHALLENGES.to_vec(),
+    }
+}
 
 fn main() -> anyhow::Result<()> {
     env_logger::init();
     let args: Args = cli::Args::parse();
+    let registry = registry();
     match args {
         Args {
-            day: 1,
-            challenge: 1,
-            data_path,
-        } => {
-            let calories =
-                solve_01::solve_day_1_challenges(&data_path.as_path().join("01.txt"), 1)?;
-            println!("{:?}", calories);
-        }
-        Args {
-            day: 1,
-            challenge: 2,
-            data_path,
-        } => {
-            let calories =
-                solve_01::solve_day_1_challenges(&data_path.as_path().join("01.txt"), 3)?;
-            println!("{:?}", calories);
-        }
-        Args {
-            day: 2,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_02::solve_day_2_challenge_1(&data_path.as_path().join("02.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 2,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_02::solve_day_2_challenge_2(&data_path.as_path().join("02.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 3,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_03::solve_day_3_challenge_1(&data_path.as_path().join("03.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 3,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_03::solve_day_3_challenge_2(&data_path.as_path().join("03.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 4,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_04::solve_day_4_challenge_1(&data_path.as_path().join("04.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 4,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_04::solve_day_4_challenge_2(&data_path.as_path().join("04.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 5,
-            challenge: 1,
-            data_path,
-        } => {
-            let score =
-                solve_05::solve_day_5_challenge_1(&data_path.as_path().join("05.txt"), false)?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 5,
-            challenge: 2,
-            data_path,
-        } => {
-            let score =
-                solve_05::solve_day_5_challenge_1(&data_path.as_path().join("05.txt"), true)?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 6,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_06::solve_day_6_challenge_1(&data_path.as_path().join("06.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 6,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_06::solve_day_6_challenge_2(&data_path.as_path().join("06.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 7,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_07::solve_day_7_challenge_1(&data_path.as_path().join("07.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 7,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_07::solve_day_7_challenge_2(&data_path.as_path().join("07.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 8,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_08::solve_day_8_challenge_1(&data_path.as_path().join("08.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 8,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_08::solve_day_8_challenge_2(&data_path.as_path().join("08.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 9,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_09::solve_day_9_challenge_1(&data_path.as_path().join("09.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 9,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_09::solve_day_9_challenge_2(&data_path.as_path().join("09.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 10,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_10::solve_day_10_challenge_1(&data_path.as_path().join("10.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 10,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_10::solve_day_10_challenge_2(&data_path.as_path().join("10.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 11,
-            challenge: 1,
-            data_path: _,
-        } => {
-            let score = solve_11::solve_day_11_challenge_1()?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 11,
-            challenge: 2,
-            data_path: _,
-        } => {
-            let score = solve_11::solve_day_11_challenge_2()?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 12,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_12::solve_day_12_challenge_1(&data_path.as_path().join("12.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 12,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_12::solve_day_12_challenge_2(&data_path.as_path().join("12.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 14,
-            challenge: 1,
-            data_path,
-        } => {
-            let score = solve_14::solve_day_14_challenge_1(&data_path.as_path().join("14.txt"))?;
-            println!("{:?}", score);
-        }
-        Args {
-            day: 14,
-            challenge: 2,
-            data_path,
-        } => {
-            let score = solve_14::solve_day_14_challenge_2(&data_path.as_path().join("14.txt"))?;
-            println!("{:?}", score);
-        }
-        Args { day, challenge, .. } => {
-            eprintln!(
-                "no solver available for day {}, challenge {}",
-                day, challenge
+            command: Some(Command::Verify { answers }),
+            day,
+            challenge,
+            data_path,
+            ..
+        } => {
+            let answers = answers.unwrap_or_else(|| data_path.as_path().join("answers.toml"));
+            let expected: Vec<Expected> = verify::load_manifest(&answers)?
+                .into_iter()
+                .filter(|e| day.is_none_or(|day| e.day == day))
+                .filter(|e| challenge.is_none_or(|challenge| e.challenge == challenge))
+                .collect();
+            verify::verify(&registry, &expected, &data_path)?;
+        }
+        Args {
+            command: Some(Command::Bench { iterations }),
+            day,
+            challenge,
+            data_path,
+            input,
+            format,
+        } => {
+            let challenges = select_challenges(challenge);
+            let mut benchmarks: Vec<Benchmark> = vec![];
+            for (day, input_source) in select_days(&registry, day, &data_path, input)? {
+                let puzzle = registry.get(day).expect("selected days are registered");
+                benchmarks.extend(bench_day(
+                    puzzle,
+                    day,
+                    &challenges,
+                    &input_source,
+                    iterations,
+                )?);
+            }
+            print_benchmarks(&benchmarks, format)?;
+        }
+        Args {
+            command:
+                Some(Command::Fetch {
+                    config,
+                    base_url,
+                    interval,
+                }),
+            day,
+            data_path,
+            ..
+        } => {
+            let config = match config.or_else(fetch::default_config_path) {
+                Some(path) => fetch::load_config(&path)?,
+                None => fetch::Config::default(),
+            };
+            let session =
+                fetch::session_token(std::env::var(fetch::SESSION_ENV_VAR).ok(), &config)?;
+            let base_url = base_url
+                .or(config.base_url)
+                .unwrap_or_else(|| fetch::DEFAULT_BASE_URL.to_string());
+            let days: Vec<usize> = match day {
+                Some(day) if registry.get(day).is_none() => {
+                    bail!("no solver available for day {}", day)
+                }
+                Some(day) => vec![day],
+                None => registry.days().collect(),
+            };
+            let mut fetcher = Fetcher::new(
+                UreqClient::new(),
+                &base_url,
+                &session,
+                &data_path,
+                Duration::from_secs(interval),
             );
+            for day in days {
+                match fetcher.fetch(day)? {
+                    Fetched::Downloaded(path) => {
+                        println!("day {}: downloaded {}", day, path.display())
+                    }
+                    Fetched::Cached(path) => {
+                        println!("day {}: {} is already cached", day, path.display())
+                    }
+                }
+            }
+        }
+        Args {
+            command: None,
+            day: Some(day),
+            challenge: Some(challenge),
+            data_path,
+            input,
+            format: Format::Text,
+        } => {
+            let puzzle = registry
+                .get(day)
+                .ok_or_else(|| anyhow!("no solver available for day {}", day))?;
+            let input_source = input.unwrap_or_else(|| default_input_source(&data_path, day));
+            let input = input_source.read_to_string()?;
+            let parsed = puzzle
+                .parse(input.as_str())
+                .map_err(|e| parse_failure(&input_source, &e))?;
+            let answer = parsed.solve(challenge)?;
+            println!("{}", answer);
+        }
+        Args {
+            command: None,
+            day,
+            challenge,
+            data_path,
+            input,
+            format,
+        } => {
+            let challenges = select_challenges(challenge);
+            let mut runs: Vec<Run> = vec![];
+            for (day, input_source) in select_days(&registry, day, &data_path, input)? {
+                let puzzle = registry.get(day).expect("selected days are registered");
+                runs.extend(run_day(puzzle, day, &challenges, &input_source));
+            }
+            print_runs(&runs, format)?;
+            let failed = runs.iter().filter(|run| run.answer.is_err()).count();
+            if failed > 0 {
+                bail!("{} out of {} challenges failed", failed, runs.len());
+            }
         }
     }
     Ok(())
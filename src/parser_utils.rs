@@ -1,16 +1,449 @@
-use nom::bytes::complete::take_while_m_n;
-use nom::character::complete::digit1;
-use nom::combinator::map_res;
+use crate::grid::Grid;
+use nom::bytes::complete::{tag, take_while_m_n};
+use nom::character::complete::{char, digit1, multispace0, space0};
+use nom::combinator::{all_consuming, map_res, opt, recognize};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, terminated};
 use nom::IResult;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-fn number(input: &str) -> IResult<&str, &str> {
-    digit1(input)
+// past that many errors only their count is reported
+const REPORTED_ERRORS: usize = 10;
+
+// malformed puzzle input, lines and columns start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>, // parsers only see the input, the caller knows where it comes from
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String, // rest of the line where parsing stopped
+}
+
+impl ParseError {
+    // remaining must be a slice of input, usually where a parser stopped
+    pub fn at(input: &str, remaining: &str, expected: &str) -> ParseError {
+        let offset = (remaining.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| input.is_char_boundary(*offset))
+            .unwrap_or(input.len());
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |index| index + 1);
+        ParseError {
+            file: None,
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            expected: expected.to_string(),
+            found: input[offset..].lines().next().unwrap_or("").to_string(),
+        }
+    }
+
+    pub fn from_nom(
+        input: &str,
+        error: nom::Err<nom::error::Error<&str>>,
+        expected: &str,
+    ) -> ParseError {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::at(input, e.input, expected),
+            nom::Err::Incomplete(_) => ParseError::at(input, &input[input.len()..], expected),
+        }
+    }
+
+    // relocates an error found while parsing section, a slice of input
+    pub fn within(self, input: &str, section: &str) -> ParseError {
+        let start = ParseError::at(input, section, "");
+        ParseError {
+            column: if self.line == 1 {
+                start.column + self.column - 1
+            } else {
+                self.column
+            },
+            line: start.line + self.line - 1,
+            ..self
+        }
+    }
+
+    pub fn in_file(self, file: impl Into<String>) -> ParseError {
+        ParseError {
+            file: Some(file.into()),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}", self.line, self.column)?,
+        }
+        if self.found.is_empty() {
+            write!(f, ": expected {}, found end of line", self.expected)
+        } else {
+            write!(f, ": expected {}, found {:?}", self.expected, self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// every malformed part of the input, never empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl ParseErrors {
+    pub fn within(self, input: &str, section: &str) -> ParseErrors {
+        ParseErrors(
+            self.0
+                .into_iter()
+                .map(|error| error.within(input, section))
+                .collect(),
+        )
+    }
+
+    pub fn in_file(self, file: &str) -> ParseErrors {
+        ParseErrors(
+            self.0
+                .into_iter()
+                .map(|error| error.in_file(file))
+                .collect(),
+        )
+    }
+}
+
+impl From<ParseError> for ParseErrors {
+    fn from(error: ParseError) -> Self {
+        ParseErrors(vec![error])
+    }
+}
+
+impl Display for ParseErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, error) in self.0.iter().take(REPORTED_ERRORS).enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        if self.0.len() > REPORTED_ERRORS {
+            write!(f, "\n... and {} more", self.0.len() - REPORTED_ERRORS)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {}
+
+fn collected<O>(outputs: Vec<O>, errors: Vec<ParseError>) -> Result<Vec<O>, ParseErrors> {
+    if errors.is_empty() {
+        Ok(outputs)
+    } else {
+        Err(ParseErrors(errors))
+    }
+}
+
+// the whole input must be consumed, except for trailing whitespace
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    expected: &str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::from_nom(input, e, expected))
+}
+
+// each line must be consumed entirely, every malformed line is reported and none is skipped
+pub fn parse_lines<'a, O>(
+    input: &'a str,
+    expected: &str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<Vec<O>, ParseErrors> {
+    let mut outputs = vec![];
+    let mut errors = vec![];
+    for line in input.lines() {
+        match all_consuming(&mut parser)(line) {
+            Ok((_, output)) => outputs.push(output),
+            Err(e) => errors.push(ParseError::from_nom(input, e, expected)),
+        }
+    }
+    collected(outputs, errors)
+}
+
+// runs of non blank lines, each block keeps its trailing newline
+fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..offset]);
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..]);
+    }
+    blocks
+}
+
+// blocks separated by blank lines, errors are located in the whole input
+pub fn parse_blocks<'a, O, E: Into<ParseErrors>>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> Result<O, E>,
+) -> Result<Vec<O>, ParseErrors> {
+    let mut outputs = vec![];
+    let mut errors = vec![];
+    for block in blocks(input) {
+        match parser(block) {
+            Ok(output) => outputs.push(output),
+            Err(e) => errors.extend(e.into().within(input, block).0),
+        }
+    }
+    collected(outputs, errors)
+}
+
+// one row per line, one cell per char, rows must be as wide as the first one
+pub fn parse_grid<T>(
+    input: &str,
+    expected: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Grid<T>, ParseErrors> {
+    let ncols = input.lines().next().map_or(0, |line| line.chars().count());
+    let mut rows = vec![];
+    let mut errors = vec![];
+    for line in input.lines() {
+        let mut row = Vec::with_capacity(ncols);
+        for (index, c) in line.char_indices() {
+            match cell(c) {
+                Some(value) => row.push(value),
+                None => errors.push(ParseError::at(input, &line[index..], expected)),
+            }
+        }
+        let width = line.chars().count();
+        if width < ncols {
+            let columns = format!("{} columns", ncols);
+            errors.push(ParseError::at(input, &line[line.len()..], &columns));
+        } else if let Some((index, _)) = line.char_indices().nth(ncols) {
+            errors.push(ParseError::at(input, &line[index..], "end of line"));
+        }
+        rows.push(row);
+    }
+    if !errors.is_empty() {
+        return Err(ParseErrors(errors));
+    }
+    Ok(Grid::from_rows(rows).expect("rows of the same width"))
+}
+
+// unsigned integer of any width, out of range values do not parse
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse::<T>)(input)
+}
+
+// signed integer of any width, with an optional leading minus sign
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse::<T>)(input)
 }
 
-pub(crate) fn positive_number(input: &str) -> IResult<&str, usize> {
-    map_res(number, |out| out.parse::<usize>())(input)
+pub fn positive_number(input: &str) -> IResult<&str, usize> {
+    unsigned(input)
 }
 
-pub(crate) fn single_space(input: &str) -> IResult<&str, &str> {
+pub fn signed_number(input: &str) -> IResult<&str, i64> {
+    signed(input)
+}
+
+// `,` optionally followed by spaces
+pub fn comma(input: &str) -> IResult<&str, char> {
+    terminated(char(','), space0)(input)
+}
+
+// `->` optionally surrounded by spaces
+pub fn arrow(input: &str) -> IResult<&str, &str> {
+    delimited(space0, tag("->"), space0)(input)
+}
+
+pub fn comma_separated<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(comma, item)
+}
+
+pub fn arrow_separated<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(arrow, item)
+}
+
+pub fn single_space(input: &str) -> IResult<&str, &str> {
     take_while_m_n(1, 1, |c: char| c == ' ')(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Pos;
+    use crate::parser_utils::{
+        arrow_separated, comma_separated, parse_all, parse_blocks, parse_grid, parse_lines,
+        positive_number, signed, signed_number, unsigned, ParseError,
+    };
+    use nom::character::complete::char;
+    use nom::multi::separated_list1;
+    use nom::sequence::separated_pair;
+
+    #[test]
+    fn test_signed_number() {
+        assert_eq!(signed_number("42,"), Ok((",", 42)));
+        assert_eq!(signed_number("-42"), Ok(("", -42)));
+        assert!(signed_number("-").is_err());
+        assert!(signed_number("+1").is_err());
+    }
+
+    #[test]
+    fn test_integers_of_any_width() {
+        assert_eq!(signed::<i8>("-128"), Ok(("", -128)));
+        assert!(signed::<i8>("128").is_err());
+        assert_eq!(
+            signed::<i128>("-170141183460469231731687303715884105728 "),
+            Ok((" ", i128::MIN))
+        );
+        assert_eq!(unsigned::<u32>("4294967295"), Ok(("", u32::MAX)));
+        assert!(unsigned::<u32>("4294967296").is_err());
+        assert!(unsigned::<u64>("-1").is_err());
+    }
+
+    #[test]
+    fn test_separated_lists() {
+        assert_eq!(
+            comma_separated(positive_number)("79, 98,3 rest"),
+            Ok((" rest", vec![79, 98, 3]))
+        );
+        let point = || separated_pair(positive_number, char(','), positive_number);
+        assert_eq!(
+            arrow_separated(point())("498,4 -> 498,6->496,6"),
+            Ok(("", vec![(498, 4), (498, 6), (496, 6)]))
+        );
+        assert_eq!(
+            arrow_separated(point())("1,2 -> x"),
+            Ok((" -> x", vec![(1, 2)]))
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines("1\n22\n", "a number", positive_number),
+            Ok(vec![1, 22])
+        );
+        let errors = parse_lines("1\n22\n3x3\n4\n", "a number", positive_number).unwrap_err();
+        assert_eq!(
+            errors.0,
+            vec![ParseError {
+                file: None,
+                line: 3,
+                column: 2,
+                expected: "a number".to_string(),
+                found: "x3".to_string(),
+            }]
+        );
+        // blank lines are reported, not skipped
+        let errors = parse_lines("1\n\n2\n", "a number", positive_number).unwrap_err();
+        assert_eq!((errors.0[0].line, errors.0[0].column), (2, 1));
+        assert_eq!(
+            errors.to_string(),
+            "line 2, column 1: expected a number, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_collects_errors() {
+        let errors = parse_lines("a\n1\nb\n2\nc\n", "a number", positive_number)
+            .unwrap_err()
+            .in_file("data/00.txt");
+        assert_eq!(
+            errors.to_string(),
+            "data/00.txt:1:1: expected a number, found \"a\"\n\
+             data/00.txt:3:1: expected a number, found \"b\"\n\
+             data/00.txt:5:1: expected a number, found \"c\""
+        );
+        let input = "x\n".repeat(12);
+        let errors = parse_lines(&input, "a number", positive_number).unwrap_err();
+        assert_eq!(errors.0.len(), 12);
+        assert!(errors.to_string().ends_with("\n... and 2 more"));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let numbers = separated_list1(char(','), positive_number);
+        assert_eq!(
+            parse_all("1,2,3\n\n", "numbers", numbers),
+            Ok(vec![1, 2, 3])
+        );
+        let error = parse_all(
+            "1,2\n,é,3\n",
+            "numbers",
+            separated_list1(char(','), positive_number),
+        )
+        .unwrap_err()
+        .in_file("data/00.txt");
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.to_string(),
+            "data/00.txt:2:1: expected numbers, found \",é,3\""
+        );
+        let error = ParseError::at("ab\n,é,x", &"ab\n,é,x"[7..], "a number");
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 4, "x")
+        );
+        let input = "header\n1\n2x\n";
+        let errors = parse_lines(&input[7..], "a number", positive_number)
+            .unwrap_err()
+            .within(input, &input[7..]);
+        assert_eq!((errors.0[0].line, errors.0[0].column), (3, 2));
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let sums = parse_blocks("1\n2\n\n\n3\n \n4\n5", |block| {
+            parse_lines(block, "a number", positive_number)
+                .map(|numbers| numbers.iter().sum::<usize>())
+        });
+        assert_eq!(sums, Ok(vec![3, 3, 9]));
+        let errors = parse_blocks("1\n2\n\n3\nx\n\ny\n", |block| {
+            parse_lines(block, "a number", positive_number)
+        })
+        .unwrap_err();
+        let locations: Vec<(usize, usize)> = errors
+            .0
+            .iter()
+            .map(|error| (error.line, error.column))
+            .collect();
+        assert_eq!(locations, vec![(5, 1), (7, 1)]);
+        assert_eq!(
+            parse_blocks("\n\n", |block| parse_all(
+                block,
+                "a number",
+                positive_number
+            )),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid("12\n34\n", "a digit", |c| c.to_digit(10)).unwrap();
+        assert_eq!(grid[Pos::new(1, 0)], 3);
+        let errors = parse_grid("12\n3\n4x6\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            errors.to_string(),
+            "line 2, column 2: expected 2 columns, found end of line\n\
+             line 3, column 2: expected a digit, found \"x6\"\n\
+             line 3, column 3: expected end of line, found \"6\""
+        );
+    }
+}
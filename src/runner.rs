@@ -0,0 +1,189 @@
+use crate::cli::Format;
+use crate::input::InputSource;
+use anyhow::anyhow;
+use aoc_2022::parser_utils::{ParseError, ParseErrors};
+use aoc_2022::solver::{Answer, Puzzle};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub(crate) struct Run {
+    pub day: usize,
+    pub challenge: usize,
+    pub answer: anyhow::Result<Answer>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+// reads and parses the input once then solves each challenge, failures are recorded instead of returned
+pub(crate) fn run_day(
+    puzzle: &dyn Puzzle,
+    day: usize,
+    challenges: &[usize],
+    input_source: &InputSource,
+) -> Vec<Run> {
+    let input = match input_source.read_to_string() {
+        Ok(input) => input,
+        Err(e) => {
+            return challenges
+                .iter()
+                .map(|&challenge| Run {
+                    day,
+                    challenge,
+                    answer: Err(anyhow!("failed to read {}: {:#}", input_source, e)),
+                    parse_time: Duration::ZERO,
+                    solve_time: Duration::ZERO,
+                })
+                .collect()
+        }
+    };
+    let start = Instant::now();
+    let parsed = puzzle.parse(input.as_str());
+    let parse_time = start.elapsed();
+    match parsed {
+        Ok(parsed) => challenges
+            .iter()
+            .map(|&challenge| {
+                let start = Instant::now();
+                let answer = parsed.solve(challenge);
+                Run {
+                    day,
+                    challenge,
+                    answer,
+                    parse_time,
+                    solve_time: start.elapsed(),
+                }
+            })
+            .collect(),
+        Err(e) => challenges
+            .iter()
+            .map(|&challenge| Run {
+                day,
+                challenge,
+                answer: Err(parse_failure(input_source, &e)),
+                parse_time,
+                solve_time: Duration::ZERO,
+            })
+            .collect(),
+    }
+}
+
+// malformed input is reported at its location in the input file
+pub(crate) fn parse_failure(input_source: &InputSource, error: &anyhow::Error) -> anyhow::Error {
+    let file = input_source.to_string();
+    if let Some(errors) = error.downcast_ref::<ParseErrors>() {
+        anyhow!(errors.clone().in_file(&file))
+    } else if let Some(error) = error.downcast_ref::<ParseError>() {
+        anyhow!(error.clone().in_file(file))
+    } else {
+        anyhow!("failed to parse {}: {:#}", input_source, error)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Record<'a> {
+    pub day: usize,
+    pub part: usize,
+    pub answer: Option<&'a Answer>,
+    pub elapsed: f64, // seconds spent parsing and solving
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl<'a> From<&'a Run> for Record<'a> {
+    fn from(run: &'a Run) -> Self {
+        Record {
+            day: run.day,
+            part: run.challenge,
+            answer: run.answer.as_ref().ok(),
+            elapsed: (run.parse_time + run.solve_time).as_secs_f64(),
+            error: run.answer.as_ref().err().map(|e| format!("{:#}", e)),
+        }
+    }
+}
+
+// quotes fields containing separators, quotes or line breaks, such as the day 10 CRT image
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+pub(crate) fn print_runs(runs: &[Run], format: Format) -> anyhow::Result<()> {
+    match format {
+        Format::Text => print_summary(runs),
+        Format::Json => {
+            let records: Vec<Record> = runs.iter().map(Record::from).collect();
+            println!("{}", serde_json::to_string_pretty(&records)?);
+        }
+        Format::Csv => {
+            println!("day,part,answer,elapsed,error");
+            for record in runs.iter().map(Record::from) {
+                let answer = match record.answer {
+                    Some(Answer::NotFound) | None => String::new(),
+                    Some(answer) => answer.to_string(),
+                };
+                println!(
+                    "{},{},{},{},{}",
+                    record.day,
+                    record.part,
+                    csv_field(&answer),
+                    record.elapsed,
+                    csv_field(record.error.as_deref().unwrap_or_default())
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_summary(runs: &[Run]) {
+    println!(
+        "{:>3}  {:>9}  {:<20}  {:>12}  {:>12}",
+        "day", "challenge", "answer", "parse", "solve"
+    );
+    for run in runs {
+        let answer = match &run.answer {
+            Ok(Answer::Text(text)) if text.contains('\n') => String::from("(see below)"),
+            Ok(answer) => answer.to_string(),
+            Err(_) => String::from("FAILED"),
+        };
+        println!(
+            "{:>3}  {:>9}  {:<20}  {:>12.2?}  {:>12.2?}",
+            run.day, run.challenge, answer, run.parse_time, run.solve_time
+        );
+    }
+    for run in runs {
+        if let Ok(Answer::Text(text)) = &run.answer {
+            if text.contains('\n') {
+                println!();
+                println!("day {} challenge {}:", run.day, run.challenge);
+                println!("{}", text);
+            }
+        }
+    }
+    let failures: Vec<&Run> = runs.iter().filter(|run| run.answer.is_err()).collect();
+    if !failures.is_empty() {
+        println!();
+        println!("failures:");
+        for run in failures {
+            if let Err(e) = &run.answer {
+                println!("day {} challenge {}: {:#}", run.day, run.challenge, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::csv_field;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("13680"), "13680");
+        assert_eq!(csv_field("#.\n.#"), "\"#.\n.#\"");
+        assert_eq!(csv_field("a \"b\", c"), "\"a \"\"b\"\", c\"");
+    }
+}
@@ -1,41 +1,59 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::str::FromStr;
+use crate::parser_utils::{parse_blocks, parse_lines, unsigned};
+use crate::solver::{Answer, Solver};
 
 type Calories = u64;
-pub(crate) fn solve_day_1_challenges(input_path: &Path, n: usize) -> anyhow::Result<Calories> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut elves_calories: Vec<Calories> = vec![];
-    let mut elve_calories_sum: Calories = 0;
-    for line in buffer_reader.lines() {
-        let line = line?;
-        if line.is_empty() {
-            elves_calories.push(elve_calories_sum);
-            elve_calories_sum = 0; // reset calories
-        } else {
-            elve_calories_sum += u64::from_str(line.as_str())?;
-        }
-    }
+
+// one block of lines per elf
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Calories>> {
+    Ok(parse_blocks(input, |block| {
+        parse_lines(block, "a number of calories", unsigned::<Calories>)
+            .map(|calories| calories.iter().sum())
+    })?)
+}
+
+pub fn solve_day_1_challenges(elves_calories: &[Calories], n: usize) -> Calories {
+    let mut elves_calories = elves_calories.to_vec();
     elves_calories.sort_unstable_by(|a, b| b.cmp(a)); // sort decreasing
-    Ok(elves_calories.iter().take(n).sum())
+    elves_calories.iter().take(n).sum()
+}
+
+pub struct CalorieCounting;
+
+impl Solver for CalorieCounting {
+    type Input = Vec<Calories>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_1_challenges(input, 1).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_1_challenges(input, 3).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solve_01::solve_day_1_challenges;
-    use std::path::Path;
+    use crate::solve_01::{parse_input, solve_day_1_challenges};
+    use std::fs;
+
+    #[test]
+    fn test_solve_calorie_counting_example() {
+        let elves_calories =
+            parse_input("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n")
+                .unwrap();
+        assert_eq!(elves_calories, vec![6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(solve_day_1_challenges(&elves_calories, 1), 24000);
+        assert_eq!(solve_day_1_challenges(&elves_calories, 3), 45000)
+    }
 
     #[test]
     fn test_solve_calorie_counting() {
-        assert_eq!(
-            solve_day_1_challenges(Path::new("data/01.txt"), 1).unwrap(),
-            69310
-        );
-        assert_eq!(
-            solve_day_1_challenges(Path::new("data/01.txt"), 3).unwrap(),
-            206104
-        )
+        let elves_calories = parse_input(&fs::read_to_string("data/01.txt").unwrap()).unwrap();
+        assert_eq!(solve_day_1_challenges(&elves_calories, 1), 69310);
+        assert_eq!(solve_day_1_challenges(&elves_calories, 3), 206104)
     }
 }
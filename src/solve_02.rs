@@ -1,7 +1,9 @@
+use crate::parser_utils::{parse_lines, single_space};
+use crate::solver::{Answer, Solver};
 use log::debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use nom::character::complete::one_of;
+use nom::sequence::separated_pair;
+use nom::IResult;
 
 type Score = u64;
 
@@ -98,24 +100,39 @@ fn score_round(player: &Shape, opponent: &Shape) -> Score {
     score_from_outcome + score_from_shape
 }
 
-fn parse_input_file_challenge_1(input_path: &Path) -> anyhow::Result<Vec<RoundChallenge1>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut rounds: Vec<RoundChallenge1> = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        let letters: Vec<&str> = line.split(' ').collect();
-        let first_letter = letters
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("parsing error {}", line))?;
-        let second_letter = letters
-            .get(1)
-            .ok_or_else(|| anyhow::anyhow!("parsing error {}", line))?;
-        let first: Shape = shape_from_str_hopefuly(first_letter)?;
-        let second: Shape = shape_from_str_hopefuly(second_letter)?;
-        rounds.push(RoundChallenge1 { first, second })
-    }
-    Ok(rounds)
+// both letters of a line of the strategy guide, their meaning depends on the challenge
+type EncryptedRound = (String, String);
+
+fn parse_round(input: &str) -> IResult<&str, EncryptedRound> {
+    let (i, (first_letter, second_letter)) =
+        separated_pair(one_of("ABC"), single_space, one_of("XYZ"))(input)?;
+    Ok((i, (first_letter.to_string(), second_letter.to_string())))
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<EncryptedRound>> {
+    Ok(parse_lines(input, "A, B or C then X, Y or Z", parse_round)?)
+}
+
+fn decrypt_rounds_challenge_1(rounds: &[EncryptedRound]) -> anyhow::Result<Vec<RoundChallenge1>> {
+    rounds
+        .iter()
+        .map(|(first_letter, second_letter)| {
+            let first: Shape = shape_from_str_hopefuly(first_letter)?;
+            let second: Shape = shape_from_str_hopefuly(second_letter)?;
+            Ok(RoundChallenge1 { first, second })
+        })
+        .collect()
+}
+
+fn decrypt_rounds_challenge_2(rounds: &[EncryptedRound]) -> anyhow::Result<Vec<RoundChallenge2>> {
+    rounds
+        .iter()
+        .map(|(first_letter, second_letter)| {
+            let first: Shape = shape_from_str_hopefuly(first_letter)?;
+            let second: Outcome = outcome_from_str_hopefuly(second_letter)?;
+            Ok(RoundChallenge2 { first, second })
+        })
+        .collect()
 }
 
 fn score_rounds(rounds: &[RoundChallenge1]) -> Score {
@@ -126,33 +143,13 @@ fn score_rounds(rounds: &[RoundChallenge1]) -> Score {
         .sum()
 }
 
-pub(crate) fn solve_day_2_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let rounds = parse_input_file_challenge_1(input_path)?;
+pub fn solve_day_2_challenge_1(rounds: &[EncryptedRound]) -> anyhow::Result<Score> {
+    let rounds = decrypt_rounds_challenge_1(rounds)?;
     Ok(score_rounds(rounds.as_slice()))
 }
 
-fn parse_input_file_challenge_2(input_path: &Path) -> anyhow::Result<Vec<RoundChallenge2>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut rounds: Vec<RoundChallenge2> = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        let letters: Vec<&str> = line.split(' ').collect();
-        let first_letter = letters
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("parsing error {}", line))?;
-        let second_letter = letters
-            .get(1)
-            .ok_or_else(|| anyhow::anyhow!("parsing error {}", line))?;
-        let first: Shape = shape_from_str_hopefuly(first_letter)?;
-        let second: Outcome = outcome_from_str_hopefuly(second_letter)?;
-        rounds.push(RoundChallenge2 { first, second })
-    }
-    Ok(rounds)
-}
-
-pub(crate) fn solve_day_2_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let rounds = parse_input_file_challenge_2(input_path)?;
+pub fn solve_day_2_challenge_2(rounds: &[EncryptedRound]) -> anyhow::Result<Score> {
+    let rounds = decrypt_rounds_challenge_2(rounds)?;
     let rounds_from_strategy: Vec<RoundChallenge1> = rounds
         .iter()
         .map(|r| RoundChallenge1 {
@@ -163,15 +160,36 @@ pub(crate) fn solve_day_2_challenge_2(input_path: &Path) -> anyhow::Result<Score
     Ok(score_rounds(rounds_from_strategy.as_slice()))
 }
 
+pub struct RockPaperScissors;
+
+impl Solver for RockPaperScissors {
+    type Input = Vec<EncryptedRound>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_2_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_2_challenge_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::solve_02::{solve_day_2_challenge_1, solve_day_2_challenge_2};
-    use std::path::Path;
+    use crate::solve_02::{parse_input, solve_day_2_challenge_1, solve_day_2_challenge_2};
+    use std::fs;
 
     #[test]
     fn test_solve_day_2_challenge_1() {
         assert_eq!(
-            solve_day_2_challenge_1(Path::new("data/02.txt")).unwrap(),
+            solve_day_2_challenge_1(
+                &parse_input(&fs::read_to_string("data/02.txt").unwrap()).unwrap()
+            )
+            .unwrap(),
             12586
         );
     }
@@ -179,7 +197,10 @@ mod tests {
     #[test]
     fn test_solve_day_2_challenge_2() {
         assert_eq!(
-            solve_day_2_challenge_2(Path::new("data/02.txt")).unwrap(),
+            solve_day_2_challenge_2(
+                &parse_input(&fs::read_to_string("data/02.txt").unwrap()).unwrap()
+            )
+            .unwrap(),
             13193
         );
     }
@@ -1,25 +1,23 @@
+use crate::parser_utils::parse_lines;
+use crate::solver::{Answer, Solver};
 use itertools::Itertools;
 use log::debug;
+use nom::character::complete::alpha1;
+use nom::combinator::map;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 
 type Score = u64;
 
-fn parse_input_file(input_path: &Path) -> anyhow::Result<Vec<String>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut contents = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        contents.push(line);
-    }
-    Ok(contents)
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<String>> {
+    Ok(parse_lines(
+        input,
+        "items a to z or A to Z",
+        map(alpha1, String::from),
+    )?)
 }
 
-fn split_rucksack(content: &String) -> anyhow::Result<(String, String)> {
-    if (content.len() % 2) != 0 {
+fn split_rucksack(content: &str) -> anyhow::Result<(String, String)> {
+    if !content.len().is_multiple_of(2) {
         anyhow::bail!("rucksack contain uneven compartments")
     } else {
         let (first, second) = content.split_at(content.len() / 2);
@@ -44,8 +42,7 @@ fn priority(c: &char) -> Score {
     }
 }
 
-pub(crate) fn solve_day_3_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let contents = parse_input_file(input_path)?;
+pub fn solve_day_3_challenge_1(contents: &[String]) -> anyhow::Result<Score> {
     let total: anyhow::Result<Vec<Score>> = contents
         .iter()
         .map(|content| {
@@ -64,14 +61,13 @@ pub(crate) fn solve_day_3_challenge_1(input_path: &Path) -> anyhow::Result<Score
     Ok(total?.iter().sum())
 }
 
-pub(crate) fn solve_day_3_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let contents = parse_input_file(input_path)?;
+pub fn solve_day_3_challenge_2(contents: &[String]) -> anyhow::Result<Score> {
     let mut total: Score = 0;
-    for (content_0, content_1, content_2) in contents.into_iter().tuples() {
-        let intersection_0_1 = shared_item_types(&content_0, &content_1);
-        let intersection_0_2 = shared_item_types(&content_0, &content_2);
-        let first_set: HashSet<char> = HashSet::from_iter(intersection_0_1.into_iter());
-        let second_set: HashSet<char> = HashSet::from_iter(intersection_0_2.into_iter());
+    for (content_0, content_1, content_2) in contents.iter().tuples() {
+        let intersection_0_1 = shared_item_types(content_0, content_1);
+        let intersection_0_2 = shared_item_types(content_0, content_2);
+        let first_set: HashSet<char> = HashSet::from_iter(intersection_0_1);
+        let second_set: HashSet<char> = HashSet::from_iter(intersection_0_2);
         let intersection_0_1_2 = first_set.intersection(&second_set);
         debug!("{:?}", intersection_0_1_2);
         let s: Score = intersection_0_1_2
@@ -86,15 +82,36 @@ pub(crate) fn solve_day_3_challenge_2(input_path: &Path) -> anyhow::Result<Score
     Ok(total)
 }
 
+pub struct RucksackReorganization;
+
+impl Solver for RucksackReorganization {
+    type Input = Vec<String>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_3_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_3_challenge_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::solve_03::{solve_day_3_challenge_1, solve_day_3_challenge_2};
-    use std::path::Path;
+    use crate::solve_03::{parse_input, solve_day_3_challenge_1, solve_day_3_challenge_2};
+    use std::fs;
 
     #[test]
     fn test_solve_day_3_challenge_1() {
         assert_eq!(
-            solve_day_3_challenge_1(Path::new("data/03.txt")).unwrap(),
+            solve_day_3_challenge_1(
+                &parse_input(&fs::read_to_string("data/03.txt").unwrap()).unwrap()
+            )
+            .unwrap(),
             8401
         );
     }
@@ -102,7 +119,10 @@ mod tests {
     #[test]
     fn test_solve_day_3_challenge_2() {
         assert_eq!(
-            solve_day_3_challenge_2(Path::new("data/03.txt")).unwrap(),
+            solve_day_3_challenge_2(
+                &parse_input(&fs::read_to_string("data/03.txt").unwrap()).unwrap()
+            )
+            .unwrap(),
             2641
         );
     }
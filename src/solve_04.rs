@@ -1,31 +1,21 @@
+use crate::parser_utils::{comma, parse_lines, positive_number};
+use crate::solver::{Answer, Solver};
 use log::debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::str::FromStr;
+use nom::character::complete::char;
+use nom::sequence::separated_pair;
+use nom::IResult;
 
 type Score = usize;
 
 #[derive(Debug)]
-struct SectionRange {
+pub struct SectionRange {
     begin: usize,
     end: usize,
 }
 
-fn parse_range(s: &str) -> anyhow::Result<SectionRange> {
-    let range_bounds: Vec<&str> = s.split('-').collect();
-    let first_bound = range_bounds
-        .first()
-        .ok_or_else(|| anyhow::anyhow!("parsing error {}", s))?;
-    let first_bound = usize::from_str(first_bound)?;
-    let second_bound = range_bounds
-        .get(1)
-        .ok_or_else(|| anyhow::anyhow!("parsing error {}", s))?;
-    let second_bound = usize::from_str(second_bound)?;
-    Ok(SectionRange {
-        begin: first_bound,
-        end: second_bound,
-    })
+fn parse_range(input: &str) -> IResult<&str, SectionRange> {
+    let (i, (begin, end)) = separated_pair(positive_number, char('-'), positive_number)(input)?;
+    Ok((i, SectionRange { begin, end }))
 }
 
 impl SectionRange {
@@ -40,32 +30,18 @@ impl SectionRange {
     }
 }
 
-type ElfPair = (SectionRange, SectionRange);
-
-fn parse_input_file(input_path: &Path) -> anyhow::Result<Vec<ElfPair>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut assignments: Vec<ElfPair> = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        let ranges: Vec<&str> = line.split(',').collect();
-        let first_range = ranges
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("parsing error {}", line))?;
-        let first_range = parse_range(first_range)?;
-        let second_range = ranges
-            .get(1)
-            .ok_or_else(|| anyhow::anyhow!("parsing error {}", line))?;
-        let second_range = parse_range(second_range)?;
-
-        assignments.push((first_range, second_range));
-    }
-    Ok(assignments)
+pub type ElfPair = (SectionRange, SectionRange);
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<ElfPair>> {
+    Ok(parse_lines(
+        input,
+        "two section ranges separated by a comma",
+        separated_pair(parse_range, comma, parse_range),
+    )?)
 }
 
-pub(crate) fn solve_day_4_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let assignments = parse_input_file(input_path)?;
-    let score = assignments
+pub fn solve_day_4_challenge_1(assignments: &[ElfPair]) -> Score {
+    assignments
         .iter()
         .map(|(section_range_1, section_range_2)| {
             if section_range_1.inside(section_range_2) || section_range_2.inside(section_range_1) {
@@ -78,13 +54,11 @@ pub(crate) fn solve_day_4_challenge_1(input_path: &Path) -> anyhow::Result<Score
                 0
             }
         })
-        .sum();
-    Ok(score)
+        .sum()
 }
 
-pub(crate) fn solve_day_4_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let assignments = parse_input_file(input_path)?;
-    let score = assignments
+pub fn solve_day_4_challenge_2(assignments: &[ElfPair]) -> Score {
+    assignments
         .iter()
         .map(|(section_range_1, section_range_2)| {
             if section_range_1.overlap(section_range_2) {
@@ -98,6 +72,36 @@ pub(crate) fn solve_day_4_challenge_2(input_path: &Path) -> anyhow::Result<Score
                 0
             }
         })
-        .sum();
-    Ok(score)
+        .sum()
+}
+
+pub struct CampCleanup;
+
+impl Solver for CampCleanup {
+    type Input = Vec<ElfPair>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_4_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_4_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_04::{parse_input, solve_day_4_challenge_1, solve_day_4_challenge_2};
+
+    #[test]
+    fn test_solve_camp_cleanup_example() {
+        let assignments =
+            parse_input("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n").unwrap();
+        assert_eq!(solve_day_4_challenge_1(&assignments), 2);
+        assert_eq!(solve_day_4_challenge_2(&assignments), 4);
+    }
 }
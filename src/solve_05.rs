@@ -1,18 +1,17 @@
-use crate::parser_utils::{positive_number, single_space};
+use crate::parser_utils::{parse_lines, positive_number, single_space, ParseError, ParseErrors};
+use crate::solver::{Answer, Solver};
 use anyhow::{anyhow, bail};
 use log::debug;
-use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::{anychar, line_ending};
-use nom::multi::{many0, many1, separated_list0};
+use nom::bytes::complete::tag;
+use nom::character::complete::{anychar, line_ending, space0, space1};
+use nom::combinator::map;
+use nom::multi::{many1, separated_list0, separated_list1};
 use nom::sequence::{delimited, terminated, tuple};
 use nom::IResult;
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{BufReader, Read};
-use std::path::Path;
 
 #[derive(Debug, Eq, PartialEq)]
-struct Move {
+pub struct Move {
     count: usize,
     from: usize,
     to: usize,
@@ -35,10 +34,6 @@ fn parse_move_line(input: &str) -> IResult<&str, Move> {
     Ok((i, Move { count, from, to }))
 }
 
-fn parse_move_line_eol(input: &str) -> IResult<&str, Move> {
-    terminated(parse_move_line, line_ending)(input)
-}
-
 fn parse_crate_char(input: &str) -> IResult<&str, char> {
     delimited(tag("["), anychar, tag("]"))(input)
 }
@@ -61,81 +56,111 @@ fn parse_crate_line_eol(input: &str) -> IResult<&str, Vec<Option<char>>> {
     terminated(parse_crate_line, line_ending)(input)
 }
 
+type Crates = Vec<Vec<Option<char>>>;
+
 #[derive(Debug, Eq, PartialEq)]
-struct Input {
-    crates: Vec<Vec<Option<char>>>, // top -> bottom, left -> right
+pub struct Input {
+    crates: Crates, // top -> bottom, left -> right
     moves: Vec<Move>,
 }
 
-fn parse_input(input: &str) -> IResult<&str, Input> {
+fn parse_stack_numbers(input: &str) -> IResult<&str, Vec<usize>> {
+    delimited(space0, separated_list1(space1, positive_number), space0)(input)
+}
+
+// crates and the number of stacks, then a blank line, the moves that follow are left over
+fn parse_drawing(input: &str) -> IResult<&str, (Crates, usize)> {
     let (i, crates) = many1(parse_crate_line_eol)(input)?;
-    let (i, _v) = take_until("move")(i)?;
-    let (i, moves) = many0(parse_move_line_eol)(i)?;
-    Ok((i, Input { crates, moves }))
+    let (i, (numbers, _, _)) = tuple((parse_stack_numbers, line_ending, line_ending))(i)?;
+    Ok((i, (crates, numbers.len())))
 }
 
-pub(crate) fn solve_day_5_challenge_1(
-    input_path: &Path,
-    rev_stacks: bool,
-) -> anyhow::Result<String> {
-    let input_file = File::open(input_path)?;
-    let mut buffer_reader = BufReader::new(input_file);
-    let mut buffer = String::new();
-    let _read = buffer_reader.read_to_string(&mut buffer)?;
-    match parse_input(buffer.as_str()) {
-        Ok((_leftovers, input)) => {
-            let stacks_n = input.crates.first().map(|e| e.len());
-            if let Some(stacks_n) = stacks_n {
-                let mut stacks: Vec<Vec<char>> = vec![];
-                for _stack_id in 0..stacks_n {
-                    stacks.push(vec![]);
+pub fn parse_input(input: &str) -> anyhow::Result<Input> {
+    let (moves, (crates, stacks)) = parse_drawing(input)
+        .map_err(|e| ParseError::from_nom(input, e, "a row of crates or the stack numbers"))?;
+    // each move along with its line
+    let moves = parse_lines(moves, "a move", |line| {
+        map(parse_move_line, move |m| (line, m))(line)
+    })
+    .map_err(|e| e.within(input, moves))?;
+    let expected = format!("a move between stacks 1 and {}", stacks);
+    let errors: Vec<ParseError> = moves
+        .iter()
+        .filter(|(_, m)| ![m.from, m.to].iter().all(|s| (1..=stacks).contains(s)))
+        .map(|(line, _)| ParseError::at(input, line, &expected))
+        .collect();
+    if !errors.is_empty() {
+        return Err(ParseErrors(errors).into());
+    }
+    let moves = moves.into_iter().map(|(_, m)| m).collect();
+    Ok(Input { crates, moves })
+}
+
+pub fn solve_day_5_challenge_1(input: &Input, rev_stacks: bool) -> anyhow::Result<String> {
+    let stacks_n = input.crates.first().map(|e| e.len());
+    if let Some(stacks_n) = stacks_n {
+        let mut stacks: Vec<Vec<char>> = vec![];
+        for _stack_id in 0..stacks_n {
+            stacks.push(vec![]);
+        }
+        for crate_line_bottom_up in input.crates.iter().rev() {
+            for (crate_stack, crate_maybe) in crate_line_bottom_up.iter().enumerate() {
+                if let Some(crate_name) = crate_maybe {
+                    stacks
+                        .get_mut(crate_stack)
+                        .ok_or_else(|| anyhow!("stack id"))?
+                        .push(*crate_name);
                 }
-                for crate_line_bottom_up in input.crates.iter().rev() {
-                    for (crate_stack, crate_maybe) in crate_line_bottom_up.iter().enumerate() {
-                        if let Some(crate_name) = crate_maybe {
-                            stacks
-                                .get_mut(crate_stack)
-                                .ok_or_else(|| anyhow!("stack id"))?
-                                .push(*crate_name);
-                        }
-                    }
+            }
+        }
+        for m in input.moves.iter() {
+            debug!("move {} from {} to {}", m.count, m.from, m.to);
+            let from_indexed_from_0 = m.from - 1;
+            let to_indexed_from_0 = m.to - 1;
+            let from_stack = stacks
+                .get(from_indexed_from_0)
+                .ok_or_else(|| anyhow!("stack from"))?;
+            // I am not proud of the following line ^^'
+            let mut from =
+                VecDeque::from_iter(from_stack.iter().rev().take(m.count).rev().cloned());
+            debug!("{:?}", from);
+            for _c in 0..m.count {
+                if let Some(stack_to) = stacks.get_mut(to_indexed_from_0) {
+                    let v = if rev_stacks {
+                        from.pop_front().ok_or_else(|| anyhow!("stack pop"))?
+                    } else {
+                        from.pop_back().ok_or_else(|| anyhow!("stack pop"))?
+                    };
+                    debug!("{} moved from {} to {}", v, m.from, m.to);
+                    stack_to.push(v);
                 }
-                for m in input.moves.iter() {
-                    debug!("move {} from {} to {}", m.count, m.from, m.to);
-                    let from_indexed_from_0 = m.from - 1;
-                    let to_indexed_from_0 = m.to - 1;
-                    let from_stack = stacks
-                        .get(from_indexed_from_0)
-                        .ok_or_else(|| anyhow!("stack from"))?;
-                    // I am not proud of the following line ^^'
-                    let mut from =
-                        VecDeque::from_iter(from_stack.iter().rev().take(m.count).rev().cloned());
-                    debug!("{:?}", from);
-                    for _c in 0..m.count {
-                        if let Some(stack_to) = stacks.get_mut(to_indexed_from_0) {
-                            let v = if rev_stacks {
-                                from.pop_front().ok_or_else(|| anyhow!("stack pop"))?
-                            } else {
-                                from.pop_back().ok_or_else(|| anyhow!("stack pop"))?
-                            };
-                            debug!("{} moved from {} to {}", v, m.from, m.to);
-                            stack_to.push(v);
-                        }
-                        if let Some(stack_from) = stacks.get_mut(from_indexed_from_0) {
-                            stack_from.pop();
-                        }
-                    }
+                if let Some(stack_from) = stacks.get_mut(from_indexed_from_0) {
+                    stack_from.pop();
                 }
-                let top_of_each_stack: String =
-                    stacks.iter().flat_map(|s| s.last().copied()).collect();
-                return Ok(top_of_each_stack);
             }
         }
-        Err(_) => {
-            eprintln!("failed to parse input {:?}", &input_path);
-        }
+        let top_of_each_stack: String = stacks.iter().flat_map(|s| s.last().copied()).collect();
+        return Ok(top_of_each_stack);
+    }
+    bail!("no stacks of crates");
+}
+
+pub struct SupplyStacks;
+
+impl Solver for SupplyStacks {
+    type Input = Input;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_5_challenge_1(input, false)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_5_challenge_1(input, true)?.into())
     }
-    bail!("merry christmas");
 }
 
 #[cfg(test)]
@@ -193,18 +218,34 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            parse_input("[A]\n 1 \n\nmove 1 from 2 to 3\n"),
-            Ok((
-                "",
-                Input {
-                    crates: vec![vec![Some('A')]],
-                    moves: vec![Move {
-                        count: 1,
-                        from: 2,
-                        to: 3,
-                    }]
-                }
-            ))
-        )
+            parse_input("[A]        \n 1   2   3 \n\nmove 1 from 2 to 3\n").unwrap(),
+            Input {
+                crates: vec![vec![Some('A'), None, None]],
+                moves: vec![Move {
+                    count: 1,
+                    from: 2,
+                    to: 3,
+                }]
+            }
+        );
+        let error = parse_input("[A]\n 1 \n\nmove 1 from 1 to 1\nmove 1 from x to 3\n")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "line 5, column 13: expected a move, found \"x to 3\""
+        );
+        let error = parse_input("[A]\n[B\n 1 \n\n").unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "line 2, column 1: expected a row of crates or the stack numbers, found \"[B\""
+        );
+        let error = parse_input("[A]\n 1 \n\nmove 1 from 1 to 1\nmove 1 from 0 to 1\n")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "line 5, column 1: expected a move between stacks 1 and 1, found \"move 1 from 0 to 1\""
+        );
     }
 }
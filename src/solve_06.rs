@@ -1,44 +1,67 @@
+use crate::solver::{Answer, Solver};
 use itertools::Itertools;
 use log::debug;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufReader, Read};
-use std::path::Path;
 
 type Score = usize;
 
-fn parse_input(input_path: &Path) -> anyhow::Result<String> {
-    let input_file = File::open(input_path)?;
-    let mut buffer_reader = BufReader::new(input_file);
-    let mut buffer = String::new();
-    buffer_reader.read_to_string(&mut buffer)?;
-    Ok(buffer)
+pub fn parse_input(input: &str) -> anyhow::Result<String> {
+    Ok(String::from(input.trim_end()))
 }
 
-pub(crate) fn solve_day_6_challenge_1(input_path: &Path) -> anyhow::Result<Option<Score>> {
-    let stream = parse_input(input_path)?;
-    for (count, (c0, c1, c2, c3)) in stream.chars().into_iter().tuple_windows().enumerate() {
+pub fn solve_day_6_challenge_1(stream: &str) -> Option<Score> {
+    for (count, (c0, c1, c2, c3)) in stream.chars().tuple_windows().enumerate() {
         if c0 != c1 && c0 != c2 && c0 != c3 && c1 != c2 && c1 != c3 && c2 != c3 {
             debug!(
                 "found {}{}{}{} after reading {} chars",
                 c0, c1, c2, c3, count
             );
-            return Ok(Some(count + 4));
+            return Some(count + 4);
         }
     }
-    Ok(None)
+    None
 }
 
-pub(crate) fn solve_day_6_challenge_2(input_path: &Path) -> anyhow::Result<Option<Score>> {
-    let stream = parse_input(input_path)?;
-    let chars: Vec<char> = stream.chars().into_iter().collect();
-    for pos in 0..chars.len() {
-        let window = &chars.as_slice()[pos..pos + 14];
+pub fn solve_day_6_challenge_2(stream: &str) -> Option<Score> {
+    let chars: Vec<char> = stream.chars().collect();
+    for (pos, window) in chars.windows(14).enumerate() {
         let set: HashSet<&char> = HashSet::from_iter(window.iter());
         if set.len() == 14 {
             debug!("found {:?} after reading {} chars", window, pos);
-            return Ok(Some(pos + 14));
+            return Some(pos + 14);
         }
     }
-    Ok(None)
+    None
+}
+
+pub struct TuningTrouble;
+
+impl Solver for TuningTrouble {
+    type Input = String;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_6_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_6_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_06::{parse_input, solve_day_6_challenge_1, solve_day_6_challenge_2};
+
+    #[test]
+    fn test_solve_tuning_trouble_example() {
+        let stream = parse_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n").unwrap();
+        assert_eq!(solve_day_6_challenge_1(&stream), Some(7));
+        assert_eq!(solve_day_6_challenge_2(&stream), Some(19));
+        assert_eq!(solve_day_6_challenge_1("abcabc"), None);
+        assert_eq!(solve_day_6_challenge_2("abcabc"), None);
+    }
 }
@@ -1,6 +1,6 @@
-use crate::parser_utils::positive_number;
+use crate::parser_utils::{parse_lines, positive_number};
 use crate::solve_07::TerminalLine::List;
-use anyhow::bail;
+use crate::solver::{Answer, Solver};
 use itertools::Itertools;
 use log::debug;
 use nom::branch::alt;
@@ -10,12 +10,9 @@ use nom::character::streaming::multispace1;
 use nom::sequence::tuple;
 use nom::IResult;
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 
 #[derive(Debug, PartialEq)]
-pub(crate) enum TerminalLine {
+pub enum TerminalLine {
     ChangeDirectory(String),
     List,
     Directory(String),
@@ -56,20 +53,12 @@ fn parse_terminal_line(input: &str) -> IResult<&str, TerminalLine> {
     Ok((i, line))
 }
 
-pub(crate) fn parse_input(input_path: &Path) -> anyhow::Result<Vec<TerminalLine>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut terminal_lines: Vec<TerminalLine> = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        match parse_terminal_line(line.as_str()) {
-            Ok((_, terminal_line)) => terminal_lines.push(terminal_line),
-            Err(_) => {
-                bail!("failed to parse {:?}", line)
-            }
-        }
-    }
-    Ok(terminal_lines)
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<TerminalLine>> {
+    Ok(parse_lines(
+        input,
+        "a command, a directory or a file",
+        parse_terminal_line,
+    )?)
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -135,13 +124,12 @@ fn build_flat_file_structure(
     flat_file_structure
 }
 
-pub(crate) fn solve_day_7_challenge_1(input_path: &Path) -> anyhow::Result<u64> {
-    let terminal_lines = parse_input(input_path)?;
-    let flat_file_structure = build_flat_file_structure(terminal_lines.as_slice());
+pub fn solve_day_7_challenge_1(terminal_lines: &[TerminalLine]) -> u64 {
+    let flat_file_structure = build_flat_file_structure(terminal_lines);
 
-    Ok(flat_file_structure
-        .iter()
-        .map(|(abs_path, _content)| {
+    flat_file_structure
+        .keys()
+        .map(|abs_path| {
             let size = size_folder(abs_path, &flat_file_structure);
             if size <= 100_000 {
                 size
@@ -149,21 +137,21 @@ pub(crate) fn solve_day_7_challenge_1(input_path: &Path) -> anyhow::Result<u64>
                 0
             }
         })
-        .sum())
+        .sum()
 }
 
-pub(crate) fn solve_day_7_challenge_2(input_path: &Path) -> anyhow::Result<Option<u64>> {
-    let terminal_lines = parse_input(input_path)?;
-    let flat_file_structure = build_flat_file_structure(terminal_lines.as_slice());
+pub fn solve_day_7_challenge_2(terminal_lines: &[TerminalLine]) -> Option<u64> {
+    let flat_file_structure = build_flat_file_structure(terminal_lines);
 
     let total_size = size_folder(&String::from("/"), &flat_file_structure);
     let unused_space: i64 = (70_000_000 - total_size) as i64;
     let space_to_free_up: i64 = 30_000_000 - unused_space;
     debug!("space to free up: {}", space_to_free_up);
 
-    let victim = flat_file_structure
-        .iter()
-        .filter_map(|(abs_path, _content)| {
+    // smallest folder that frees up enough space
+    flat_file_structure
+        .keys()
+        .filter_map(|abs_path| {
             let size = size_folder(abs_path, &flat_file_structure);
             if size >= space_to_free_up as u64 {
                 Some(size)
@@ -171,15 +159,32 @@ pub(crate) fn solve_day_7_challenge_2(input_path: &Path) -> anyhow::Result<Optio
                 None
             }
         })
-        .min();
-    Ok(victim)
+        .min()
+}
+
+pub struct NoSpaceLeftOnDevice;
+
+impl Solver for NoSpaceLeftOnDevice {
+    type Input = Vec<TerminalLine>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_7_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_7_challenge_2(input).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::solve_07::{
-        parse_change_directory, parse_command, parse_directory, parse_file, parse_list,
-        parse_terminal_line, TerminalLine,
+        parse_change_directory, parse_command, parse_directory, parse_file, parse_input,
+        parse_list, parse_terminal_line, TerminalLine,
     };
 
     #[test]
@@ -236,4 +241,14 @@ mod tests {
             Ok(("", TerminalLine::File(1234, String::from("foo"))))
         )
     }
+
+    #[test]
+    fn test_parse_input_malformed_line() {
+        assert_eq!(
+            parse_input("$ cd /\n$ ls\n$ cp a b\n")
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 1: expected a command, a directory or a file, found \"$ cp a b\""
+        );
+    }
 }
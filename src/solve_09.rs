@@ -1,6 +1,6 @@
-use crate::parser_utils::{positive_number, single_space};
+use crate::parser_utils::{parse_lines, positive_number, single_space};
 use crate::solve_09::Direction::{Down, Left, Right, Up};
-use anyhow::bail;
+use crate::solver::{Answer, Solver};
 use itertools::repeat_n;
 use log::debug;
 use nalgebra::{Point2, Vector2};
@@ -9,9 +9,6 @@ use nom::character::complete::char;
 use nom::sequence::tuple;
 use nom::IResult;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 
 type Score = u64;
 
@@ -38,19 +35,12 @@ fn parse_direction_and_steps(input: &str) -> IResult<&str, (Direction, u64)> {
     }
 }
 
-pub fn parse_input(input_path: &Path) -> anyhow::Result<Vec<(Direction, u64)>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut directions_and_steps = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        if let Ok((_i, (direction, steps))) = parse_direction_and_steps(line.as_str()) {
-            directions_and_steps.push((direction, steps));
-        } else {
-            bail!("failed to parse {}", line);
-        }
-    }
-    Ok(directions_and_steps)
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<(Direction, u64)>> {
+    Ok(parse_lines(
+        input,
+        "a direction and a number of steps",
+        parse_direction_and_steps,
+    )?)
 }
 
 fn planckerize(direction: &Direction, steps: u64) -> impl Iterator<Item = Vector2<i64>> {
@@ -76,8 +66,7 @@ fn vector_to_head(tail_position: &Point2<i64>, head_position: &Point2<i64>) -> V
     }
 }
 
-pub fn solve_day_9_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let directions_and_steps = parse_input(input_path)?;
+pub fn solve_day_9_challenge_1(directions_and_steps: &[(Direction, u64)]) -> Score {
     let mut head_position: Point2<i64> = Point2::from([0, 0]);
     let mut tail_position: Point2<i64> = Point2::from([0, 0]);
 
@@ -86,7 +75,7 @@ pub fn solve_day_9_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
 
     for (direction, steps) in directions_and_steps {
         debug!("direction {:?}, steps: {}", direction, steps);
-        for dposition in planckerize(&direction, steps) {
+        for dposition in planckerize(direction, *steps) {
             debug!(
                 "head position: {} -> {}",
                 head_position,
@@ -104,11 +93,10 @@ pub fn solve_day_9_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
             tail_positions.insert(tail_position);
         }
     }
-    Ok(tail_positions.len() as Score)
+    tail_positions.len() as Score
 }
 
-pub fn solve_day_9_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let directions_and_steps = parse_input(input_path)?;
+pub fn solve_day_9_challenge_2(directions_and_steps: &[(Direction, u64)]) -> Score {
     let mut head_position: Point2<i64> = Point2::from([0, 0]);
     let mut tail: Vec<Point2<i64>> = repeat_n(Point2::from([0, 0]), 9).collect();
 
@@ -117,7 +105,7 @@ pub fn solve_day_9_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
 
     for (direction, steps) in directions_and_steps {
         debug!("direction {:?}, steps: {}", direction, steps);
-        for dposition in planckerize(&direction, steps) {
+        for dposition in planckerize(direction, *steps) {
             debug!(
                 "head position: {} -> {}",
                 head_position,
@@ -139,5 +127,23 @@ pub fn solve_day_9_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
             }
         }
     }
-    Ok(tail_positions.len() as Score)
+    tail_positions.len() as Score
+}
+
+pub struct RopeBridge;
+
+impl Solver for RopeBridge {
+    type Input = Vec<(Direction, u64)>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_9_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_9_challenge_2(input).into())
+    }
 }
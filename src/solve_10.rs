@@ -1,15 +1,13 @@
-use crate::parser_utils::{positive_number, single_space};
+use crate::parser_utils::{parse_lines, signed, single_space};
 use crate::solve_10::Instruction::{Addx, Noop};
+use crate::solver::{Answer, Solver};
+use itertools::Itertools;
 use log::debug;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::combinator::opt;
 use nom::sequence::tuple;
 use nom::IResult;
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 
 type Score = i64;
 
@@ -25,17 +23,7 @@ fn parse_noop(input: &str) -> IResult<&str, Instruction> {
 }
 
 fn parse_addx(input: &str) -> IResult<&str, Instruction> {
-    let (i, (_addx, _, sign, x)) = tuple((
-        tag("addx"),
-        single_space,
-        opt(nom::character::complete::char('-')),
-        positive_number,
-    ))(input)?;
-    let v = if sign.is_some() {
-        -(x as i64)
-    } else {
-        x as i64
-    };
+    let (i, (_addx, _, v)) = tuple((tag("addx"), single_space, signed))(input)?;
     Ok((i, Addx(v)))
 }
 
@@ -43,28 +31,18 @@ fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
     alt((parse_noop, parse_addx))(input)
 }
 
-pub(crate) fn parse_input(input_path: &Path) -> anyhow::Result<Vec<Instruction>> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut instructions = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        if let Ok((_, instruction)) = parse_instruction(line.as_str()) {
-            instructions.push(instruction)
-        }
-    }
-    Ok(instructions)
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Instruction>> {
+    Ok(parse_lines(input, "noop or addx", parse_instruction)?)
 }
 
 // cycle when instruction effect will take place, instruction
 type DelayedInstruction = (u64, Instruction);
 
-pub(crate) fn solve_day_10_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let instructions = parse_input(input_path)?;
+pub fn solve_day_10_challenge_1(instructions: &[Instruction]) -> Score {
     let mut delayed: VecDeque<DelayedInstruction> = VecDeque::new();
     let mut cycle: u64 = 0;
     for instruction in instructions {
-        match instruction {
+        match *instruction {
             Noop => {
                 cycle += 1;
             }
@@ -89,16 +67,15 @@ pub(crate) fn solve_day_10_challenge_1(input_path: &Path) -> anyhow::Result<Scor
         score += signal_cycle as i64 * x_register;
     }
 
-    Ok(score)
+    score
 }
 
-pub(crate) fn solve_day_10_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let instructions = parse_input(input_path)?;
+pub fn solve_day_10_challenge_2(instructions: &[Instruction]) -> String {
     let mut delayed: VecDeque<DelayedInstruction> = VecDeque::new();
     let mut cycle: u64 = 1;
 
     for instruction in instructions {
-        match instruction {
+        match *instruction {
             Noop => {
                 cycle += 1;
             }
@@ -134,9 +111,26 @@ pub(crate) fn solve_day_10_challenge_2(input_path: &Path) -> anyhow::Result<Scor
     }
 
     // debug!("pixels: {:?}", pixels);
-    for chunk in pixels.chunks(40) {
-        println!("{}", String::from_iter(chunk.iter()));
+    pixels
+        .chunks(40)
+        .map(|chunk| String::from_iter(chunk.iter()))
+        .join("\n")
+}
+
+pub struct CathodeRayTube;
+
+impl Solver for CathodeRayTube {
+    type Input = Vec<Instruction>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_10_challenge_1(input).into())
     }
 
-    Ok(0)
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_10_challenge_2(input).into())
+    }
 }
@@ -1,196 +1,310 @@
+use crate::math_utils::lcm;
+use crate::parser_utils::{comma_separated, parse_all, parse_blocks, positive_number, unsigned};
+use crate::solver::{Answer, Solver};
+use anyhow::{anyhow, bail};
 use log::debug;
-use std::cell::RefCell;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, line_ending, space0};
+use nom::combinator::map;
+use nom::sequence::{preceded, tuple};
+use nom::IResult;
 use std::collections::VecDeque;
-use std::fmt::{Debug, Formatter};
-use std::ops::Deref;
 
 type Score = i64;
 
-struct Monkey {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Constant(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add(Operand),
+    Mul(Operand),
+}
+
+impl Operation {
+    // none on overflow
+    fn apply(&self, old: i64) -> Option<i64> {
+        let value = |operand: &Operand| match operand {
+            Operand::Old => old,
+            Operand::Constant(c) => *c,
+        };
+        match self {
+            Operation::Add(operand) => old.checked_add(value(operand)),
+            Operation::Mul(operand) => old.checked_mul(value(operand)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    id: usize,
     items: VecDeque<i64>,
-    operation: Box<dyn Fn(i64) -> i64>,
-    test: Box<dyn Fn(i64) -> bool>,
-    throw: Box<dyn Fn(bool) -> usize>,
+    operation: Operation,
+    divisor: i64,
+    throw_if_true: usize,
+    throw_if_false: usize,
 }
 
-impl Debug for Monkey {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Monkey")
-            .field("items", &self.items)
-            .field("throw_if_true", &self.throw.deref()(true))
-            .field("throw_if_false", &self.throw.deref()(false))
-            .finish()
+impl Monkey {
+    fn throw(&self, worry: i64) -> usize {
+        if worry % self.divisor == 0 {
+            self.throw_if_true
+        } else {
+            self.throw_if_false
+        }
     }
 }
 
-pub fn solve_day_11_challenge_1() -> anyhow::Result<Score> {
-    let monkey_0 = Monkey {
-        items: VecDeque::from([64, 89, 65, 95]),
-        operation: Box::new(|x| x * 7),
-        test: Box::new(|x| (x % 3) == 0),
-        throw: Box::new(|b| if b { 4 } else { 1 }),
-    };
-    let monkey_1 = Monkey {
-        items: VecDeque::from([76, 66, 74, 87, 70, 56, 51, 66]),
-        operation: Box::new(|x| x + 5),
-        test: Box::new(|x| (x % 13) == 0),
-        throw: Box::new(|b| if b { 7 } else { 3 }),
-    };
-    let monkey_2 = Monkey {
-        items: VecDeque::from([91, 60, 63]),
-        operation: Box::new(|x| x * x),
-        test: Box::new(|x| (x % 2) == 0),
-        throw: Box::new(|b| if b { 6 } else { 5 }),
-    };
-    let monkey_3 = Monkey {
-        items: VecDeque::from([92, 61, 79, 97, 79]),
-        operation: Box::new(|x| x + 6),
-        test: Box::new(|x| (x % 11) == 0),
-        throw: Box::new(|b| if b { 2 } else { 6 }),
-    };
-    let monkey_4 = Monkey {
-        items: VecDeque::from([93, 54]),
-        operation: Box::new(|x| x * 11),
-        test: Box::new(|x| (x % 5) == 0),
-        throw: Box::new(|b| if b { 1 } else { 7 }),
-    };
-    let monkey_5 = Monkey {
-        items: VecDeque::from([60, 79, 92, 69, 88, 82, 70]),
-        operation: Box::new(|x| x + 8),
-        test: Box::new(|x| (x % 17) == 0),
-        throw: Box::new(|b| if b { 4 } else { 0 }),
-    };
-    let monkey_6 = Monkey {
-        items: VecDeque::from([64, 57, 73, 89, 55, 53]),
-        operation: Box::new(|x| x + 1),
-        test: Box::new(|x| (x % 19) == 0),
-        throw: Box::new(|b| if b { 0 } else { 5 }),
-    };
-    let monkey_7 = Monkey {
-        items: VecDeque::from([62]),
-        operation: Box::new(|x| x + 4),
-        test: Box::new(|x| (x % 7) == 0),
-        throw: Box::new(|b| if b { 3 } else { 2 }),
-    };
-    let mut monkeys: Vec<RefCell<Monkey>> = vec![
-        RefCell::new(monkey_0),
-        RefCell::new(monkey_1),
-        RefCell::new(monkey_2),
-        RefCell::new(monkey_3),
-        RefCell::new(monkey_4),
-        RefCell::new(monkey_5),
-        RefCell::new(monkey_6),
-        RefCell::new(monkey_7),
-    ];
-    let mut inspections = [0; 8];
-    for round in 0..20 {
-        for monkey_id in 0..8 {
-            let mut new_worries: Vec<i64> = vec![];
-            for item in monkeys[monkey_id].get_mut().items.clone() {
-                inspections[monkey_id] += 1;
-                let new_worry: i64 = monkeys[monkey_id].get_mut().operation.deref()(item);
-                let new_worry = new_worry / 3;
-                new_worries.push(new_worry);
+fn parse_operand(input: &str) -> IResult<&str, Operand> {
+    alt((
+        map(tag("old"), |_| Operand::Old),
+        map(unsigned, Operand::Constant),
+    ))(input)
+}
+
+fn parse_operation(input: &str) -> IResult<&str, Operation> {
+    let (i, (_, operator, _, operand)) = tuple((
+        tag("new = old "),
+        alt((char('+'), char('*'))),
+        char(' '),
+        parse_operand,
+    ))(input)?;
+    match operator {
+        '+' => Ok((i, Operation::Add(operand))),
+        '*' => Ok((i, Operation::Mul(operand))),
+        _ => unreachable!(), // because we match against + * using nom
+    }
+}
+
+// a `label: value` line of the monkey description, indentation is ignored
+fn field<'a, O>(
+    label: &'static str,
+    value: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(tuple((space0, tag(label), tag(": "))), value)
+}
+
+fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
+    let (i, (_, id, _, _)) =
+        tuple((tag("Monkey "), positive_number, char(':'), line_ending))(input)?;
+    let (i, (items, _)) = tuple((
+        field("Starting items", comma_separated(unsigned)),
+        line_ending,
+    ))(i)?;
+    let (i, (operation, _)) = tuple((field("Operation", parse_operation), line_ending))(i)?;
+    let (i, (divisor, _)) = tuple((
+        field("Test", preceded(tag("divisible by "), unsigned)),
+        line_ending,
+    ))(i)?;
+    let (i, (throw_if_true, _)) = tuple((
+        field(
+            "If true",
+            preceded(tag("throw to monkey "), positive_number),
+        ),
+        line_ending,
+    ))(i)?;
+    let (i, throw_if_false) = field(
+        "If false",
+        preceded(tag("throw to monkey "), positive_number),
+    )(i)?;
+    Ok((
+        i,
+        Monkey {
+            id,
+            items: items.into(),
+            operation,
+            divisor,
+            throw_if_true,
+            throw_if_false,
+        },
+    ))
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Monkey>> {
+    let monkeys = parse_blocks(input, |block| parse_all(block, "a monkey", parse_monkey))?;
+    if monkeys.is_empty() {
+        bail!("no monkeys");
+    }
+    for (index, monkey) in monkeys.iter().enumerate() {
+        if monkey.id != index {
+            bail!("expected monkey {}, found monkey {}", index, monkey.id);
+        }
+        if monkey.divisor == 0 {
+            bail!("monkey {} tests divisibility by 0", monkey.id);
+        }
+        for target in [monkey.throw_if_true, monkey.throw_if_false] {
+            if target >= monkeys.len() {
+                bail!("monkey {} throws to unknown monkey {}", monkey.id, target);
             }
-            monkeys[monkey_id].get_mut().items.clear();
-            for worry in new_worries {
-                let t: bool = monkeys[monkey_id].get_mut().test.deref()(worry);
-                let monkey_throw: usize = monkeys[monkey_id].get_mut().throw.deref()(t);
-                monkeys[monkey_throw].get_mut().items.push_back(worry);
+            if target == monkey.id {
+                bail!("monkey {} throws to itself", monkey.id);
             }
         }
-        debug!("round: {}", round);
-        for monkey in monkeys.iter_mut() {
-            debug!("monkey: {:?}", monkey.get_mut().items);
-        }
     }
-    inspections.sort();
-    debug!("{:?}", inspections);
-    Ok(inspections[6] * inspections[7])
+    Ok(monkeys)
 }
 
-pub fn solve_day_11_challenge_2() -> anyhow::Result<Score> {
-    let monkey_0 = Monkey {
-        items: VecDeque::from([64, 89, 65, 95]),
-        operation: Box::new(|x| x * 7),
-        test: Box::new(|x| (x % 3) == 0),
-        throw: Box::new(|b| if b { 4 } else { 1 }),
-    };
-    let monkey_1 = Monkey {
-        items: VecDeque::from([76, 66, 74, 87, 70, 56, 51, 66]),
-        operation: Box::new(|x| x + 5),
-        test: Box::new(|x| (x % 13) == 0),
-        throw: Box::new(|b| if b { 7 } else { 3 }),
-    };
-    let monkey_2 = Monkey {
-        items: VecDeque::from([91, 60, 63]),
-        operation: Box::new(|x| x * x),
-        test: Box::new(|x| (x % 2) == 0),
-        throw: Box::new(|b| if b { 6 } else { 5 }),
-    };
-    let monkey_3 = Monkey {
-        items: VecDeque::from([92, 61, 79, 97, 79]),
-        operation: Box::new(|x| x + 6),
-        test: Box::new(|x| (x % 11) == 0),
-        throw: Box::new(|b| if b { 2 } else { 6 }),
-    };
-    let monkey_4 = Monkey {
-        items: VecDeque::from([93, 54]),
-        operation: Box::new(|x| x * 11),
-        test: Box::new(|x| (x % 5) == 0),
-        throw: Box::new(|b| if b { 1 } else { 7 }),
-    };
-    let monkey_5 = Monkey {
-        items: VecDeque::from([60, 79, 92, 69, 88, 82, 70]),
-        operation: Box::new(|x| x + 8),
-        test: Box::new(|x| (x % 17) == 0),
-        throw: Box::new(|b| if b { 4 } else { 0 }),
-    };
-    let monkey_6 = Monkey {
-        items: VecDeque::from([64, 57, 73, 89, 55, 53]),
-        operation: Box::new(|x| x + 1),
-        test: Box::new(|x| (x % 19) == 0),
-        throw: Box::new(|b| if b { 0 } else { 5 }),
-    };
-    let monkey_7 = Monkey {
-        items: VecDeque::from([62]),
-        operation: Box::new(|x| x + 4),
-        test: Box::new(|x| (x % 7) == 0),
-        throw: Box::new(|b| if b { 3 } else { 2 }),
-    };
-    let mut monkeys: Vec<RefCell<Monkey>> = vec![
-        RefCell::new(monkey_0),
-        RefCell::new(monkey_1),
-        RefCell::new(monkey_2),
-        RefCell::new(monkey_3),
-        RefCell::new(monkey_4),
-        RefCell::new(monkey_5),
-        RefCell::new(monkey_6),
-        RefCell::new(monkey_7),
-    ];
-    let mut inspections = [0; 8];
-    for round in 0..10_000 {
-        for monkey_id in 0..8 {
-            let mut new_worries: Vec<i64> = vec![];
-            for item in monkeys[monkey_id].get_mut().items.clone() {
+fn monkey_business(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: impl Fn(i64) -> i64,
+) -> anyhow::Result<Score> {
+    let mut monkeys = monkeys.to_vec();
+    let mut inspections: Vec<Score> = vec![0; monkeys.len()];
+    for round in 0..rounds {
+        for monkey_id in 0..monkeys.len() {
+            while let Some(item) = monkeys[monkey_id].items.pop_front() {
                 inspections[monkey_id] += 1;
-                let new_worry: i64 = monkeys[monkey_id].get_mut().operation.deref()(item);
-                let new_worry = new_worry % (2 * 3 * 5 * 7 * 9 * 11 * 13 * 15 * 17 * 19);
-                new_worries.push(new_worry);
-            }
-            monkeys[monkey_id].get_mut().items.clear();
-            for worry in new_worries {
-                let t: bool = monkeys[monkey_id].get_mut().test.deref()(worry);
-                let monkey_throw: usize = monkeys[monkey_id].get_mut().throw.deref()(t);
-                monkeys[monkey_throw].get_mut().items.push_back(worry);
+                let worry = monkeys[monkey_id].operation.apply(item).ok_or_else(|| {
+                    anyhow!(
+                        "worry level overflow in round {}: monkey {} inspecting {} with {:?}",
+                        round + 1,
+                        monkey_id,
+                        item,
+                        monkeys[monkey_id].operation
+                    )
+                })?;
+                let worry = relief(worry);
+                let monkey_throw = monkeys[monkey_id].throw(worry);
+                monkeys[monkey_throw].items.push_back(worry);
             }
         }
         debug!("round: {}", round);
-        for monkey in monkeys.iter_mut() {
-            debug!("monkey: {:?}", monkey.get_mut().items);
+        for monkey in monkeys.iter() {
+            debug!("monkey {}: {:?}", monkey.id, monkey.items);
         }
     }
-    inspections.sort();
+    inspections.sort_unstable_by(|a, b| b.cmp(a)); // sort decreasing
     debug!("{:?}", inspections);
-    Ok(inspections[6] * inspections[7])
+    let first = *inspections.first().ok_or_else(|| anyhow!("no monkeys"))?;
+    let second = inspections.get(1).copied().unwrap_or(1);
+    first
+        .checked_mul(second)
+        .ok_or_else(|| anyhow!("monkey business overflow: {} * {}", first, second))
+}
+
+pub fn solve_day_11_challenge_1(monkeys: &[Monkey]) -> anyhow::Result<Score> {
+    monkey_business(monkeys, 20, |worry| worry / 3)
+}
+
+pub fn solve_day_11_challenge_2(monkeys: &[Monkey]) -> anyhow::Result<Score> {
+    // every divisibility test still holds modulo the lcm of the divisors
+    let modulus = monkeys.iter().try_fold(1, |modulus, monkey| {
+        lcm(modulus, monkey.divisor).ok_or_else(|| anyhow!("lcm of the monkey divisors overflows"))
+    })?;
+    debug!("worry modulus: {}", modulus);
+    monkey_business(monkeys, 10_000, |worry| worry % modulus)
+}
+
+pub struct MonkeyInTheMiddle;
+
+impl Solver for MonkeyInTheMiddle {
+    type Input = Vec<Monkey>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_11_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_11_challenge_2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_11::{
+        parse_input, parse_monkey, parse_operation, solve_day_11_challenge_1,
+        solve_day_11_challenge_2, Monkey, Operand, Operation,
+    };
+    use std::collections::VecDeque;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_parse_operation() {
+        assert_eq!(
+            parse_operation("new = old * old"),
+            Ok(("", Operation::Mul(Operand::Old)))
+        );
+        assert_eq!(
+            parse_operation("new = old + 6"),
+            Ok(("", Operation::Add(Operand::Constant(6))))
+        );
+    }
+
+    #[test]
+    fn test_parse_monkey() {
+        assert_eq!(
+            parse_monkey(
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3"
+            ),
+            Ok((
+                "",
+                Monkey {
+                    id: 0,
+                    items: VecDeque::from([79, 98]),
+                    operation: Operation::Mul(Operand::Constant(19)),
+                    divisor: 23,
+                    throw_if_true: 2,
+                    throw_if_false: 3,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve_day_11_example() {
+        let monkeys = parse_input(EXAMPLE).unwrap();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(solve_day_11_challenge_1(&monkeys).unwrap(), 10605);
+        assert_eq!(solve_day_11_challenge_2(&monkeys).unwrap(), 2713310158);
+    }
+
+    #[test]
+    fn test_worry_overflow() {
+        let mut monkeys = parse_input(EXAMPLE).unwrap();
+        monkeys[0].items = VecDeque::from([i64::MAX / 2]);
+        assert!(solve_day_11_challenge_1(&monkeys).is_err());
+    }
+
+    #[test]
+    fn test_no_monkeys() {
+        assert!(parse_input("").is_err());
+        assert!(solve_day_11_challenge_1(&[]).is_err());
+    }
 }
@@ -1,211 +1,77 @@
+use crate::grid::{Grid, Pos};
+use crate::parser_utils::parse_grid;
+use crate::solver::{Answer, Solver};
 use anyhow::anyhow;
-use graph::prelude::{DirectedCsrGraph, DirectedNeighbors, GraphBuilder};
-use itertools::Itertools;
 use log::debug;
-use nalgebra::{DMatrix, RowDVector};
 use pathfinding::prelude::astar;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 
 type Score = u64;
-type Heightmap = DMatrix<i32>;
+pub type Heightmap = Grid<char>;
 
-pub(crate) fn parse_input(input_path: &Path) -> anyhow::Result<Heightmap> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let rows: Vec<RowDVector<i32>> = buffer_reader
-        .lines()
-        .flat_map(|line| {
-            if let Ok(line) = line {
-                let heights = line.chars().map(|c| c as i32).into_iter();
-                Some(RowDVector::from_iterator(line.len(), heights))
-            } else {
-                None
-            }
-        })
-        .collect();
-    let heightmap: Heightmap = DMatrix::from_rows(rows.as_slice());
-    Ok(heightmap)
+pub fn parse_input(input: &str) -> anyhow::Result<Heightmap> {
+    Ok(parse_grid(input, "a height, S or E", |c| match c {
+        'a'..='z' | 'S' | 'E' => Some(c),
+        _ => None,
+    })?)
 }
 
-pub(crate) fn solve_day_12_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let heightmap = parse_input(input_path)?;
-
-    fn reindex(r: i32, c: i32, ncols: i32) -> i32 {
-        c + r * ncols
-    }
-
-    fn deindex(index: i32, ncols: i32) -> (i32, i32) {
-        (index / ncols, index % ncols)
+fn height(c: char) -> i32 {
+    match c {
+        'S' => 'a' as i32,
+        'E' => 'z' as i32,
+        c => c as i32,
     }
+}
 
-    let mut edges: Vec<(i32, i32)> = vec![];
-    let mut start: Option<(i32, i32)> = None;
-    let mut end: Option<(i32, i32)> = None;
-    for r in 0..heightmap.nrows() {
-        for c in 0..heightmap.ncols() {
-            let current = heightmap[(r, c)];
-            if current == 'S' as i32 {
-                start = Some((r as i32, c as i32))
-            }
-            if current == 'E' as i32 {
-                end = Some((r as i32, c as i32))
-            }
-
-            for (dr, dc) in [(-1i32, 0i32), (1i32, 0i32), (0i32, -1i32), (0i32, 1i32)] {
-                if (r as i32 + dr) >= 0
-                    && (r as i32 + dr < heightmap.nrows() as i32)
-                    && (c as i32 + dc >= 0)
-                    && (c as i32 + dc < heightmap.ncols() as i32)
-                {
-                    let other = heightmap[(r + dr as usize, c + dc as usize)];
+fn find(heightmap: &Heightmap, target: char) -> anyhow::Result<Pos> {
+    heightmap
+        .iter()
+        .find(|(_pos, c)| **c == target)
+        .map(|(pos, _c)| pos)
+        .ok_or_else(|| anyhow!("no {:?} in heightmap", target))
+}
 
-                    let current = if current == 'S' as i32 {
-                        'a' as i32
-                    } else {
-                        current
-                    };
-                    let other = if other == 'E' as i32 {
-                        'z' as i32
-                    } else {
-                        other
-                    };
+// climbing at most one step higher, descending is always allowed
+fn successors(heightmap: &Heightmap, pos: Pos) -> Vec<(Pos, u32)> {
+    let current = height(heightmap[pos]);
+    heightmap
+        .neighbours4(pos)
+        .filter(|other| current + 1 >= height(heightmap[*other]))
+        .map(|other| (other, 1))
+        .collect()
+}
 
-                    if current + 1 >= other {
-                        edges.push((
-                            reindex(r as i32, c as i32, heightmap.ncols() as i32),
-                            reindex(
-                                (r as i32 + dr) as i32,
-                                (c as i32 + dc) as i32,
-                                heightmap.ncols() as i32,
-                            ),
-                        ));
-                    }
-                }
-            }
-        }
-    }
-    let graph: DirectedCsrGraph<i32> = GraphBuilder::new().edges(edges).build();
-    let start = start.expect("start");
-    let end = end.expect("end");
-    let start = reindex(start.0, start.1, heightmap.ncols() as i32);
-    let end = reindex(end.0, end.1, heightmap.ncols() as i32);
-    let path = astar(
+fn shortest_path(heightmap: &Heightmap, start: Pos, end: Pos) -> Option<(Vec<Pos>, u32)> {
+    astar(
         &start,
-        |&index| {
-            graph
-                .out_neighbors(index)
-                .map(|p| (*p, 1))
-                .collect::<Vec<(i32, u32)>>()
-        },
-        |p| {
-            let (rp, cp) = deindex(*p, heightmap.ncols() as i32);
-            let (ro, co) = deindex(end, heightmap.ncols() as i32);
-            (rp.abs_diff(ro) + cp.abs_diff(co)) as u32
-        },
-        |p| *p == end,
-    );
-    if let Some((path, cost)) = path {
-        let mut matrix = DMatrix::<i32>::zeros(heightmap.nrows(), heightmap.ncols());
-        for index in path.iter() {
-            let (r, c) = deindex(*index, heightmap.ncols() as i32);
-            matrix[(r as usize, c as usize)] = 1;
-            debug!(
-                "{:?} {:?}",
-                (r, c),
-                heightmap[(r as usize, c as usize)] as u8 as char
-            );
-        }
-        for row in matrix.row_iter() {
-            println!("{:?}", row.iter().join(""));
+        |&pos| successors(heightmap, pos),
+        |pos| pos.manhattan_distance(&end) as u32,
+        |pos| *pos == end,
+    )
+}
+
+pub fn solve_day_12_challenge_1(heightmap: &Heightmap) -> anyhow::Result<Score> {
+    let start = find(heightmap, 'S')?;
+    let end = find(heightmap, 'E')?;
+    if let Some((path, cost)) = shortest_path(heightmap, start, end) {
+        let mut visited: Grid<char> = heightmap.map(|_| '.');
+        for pos in path.iter() {
+            visited[*pos] = heightmap[*pos];
+            debug!("{:?} {:?}", pos, heightmap[*pos]);
         }
+        debug!("\n{}", visited);
         Ok(cost as u64)
     } else {
         Err(anyhow!("no path found"))
     }
 }
 
-pub(crate) fn solve_day_12_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let heightmap = parse_input(input_path)?;
-
-    fn reindex(r: i32, c: i32, ncols: i32) -> i32 {
-        c + r * ncols
-    }
-
-    fn deindex(index: i32, ncols: i32) -> (i32, i32) {
-        (index / ncols, index % ncols)
-    }
-
-    let mut edges: Vec<(i32, i32)> = vec![];
-    let mut starts: Vec<(i32, i32)> = vec![];
-    let mut end: Option<(i32, i32)> = None;
-    for r in 0..heightmap.nrows() {
-        for c in 0..heightmap.ncols() {
-            let current = heightmap[(r, c)];
-            if current == 'S' as i32 || current == 'a' as i32 {
-                starts.push((r as i32, c as i32));
-            }
-            if current == 'E' as i32 {
-                end = Some((r as i32, c as i32))
-            }
-
-            for (dr, dc) in [(-1i32, 0i32), (1i32, 0i32), (0i32, -1i32), (0i32, 1i32)] {
-                if (r as i32 + dr) >= 0
-                    && (r as i32 + dr < heightmap.nrows() as i32)
-                    && (c as i32 + dc >= 0)
-                    && (c as i32 + dc < heightmap.ncols() as i32)
-                {
-                    let other = heightmap[(r + dr as usize, c + dc as usize)];
-
-                    let current = if current == 'S' as i32 {
-                        'a' as i32
-                    } else {
-                        current
-                    };
-                    let other = if other == 'E' as i32 {
-                        'z' as i32
-                    } else {
-                        other
-                    };
-
-                    if current + 1 >= other {
-                        edges.push((
-                            reindex(r as i32, c as i32, heightmap.ncols() as i32),
-                            reindex(
-                                (r as i32 + dr) as i32,
-                                (c as i32 + dc) as i32,
-                                heightmap.ncols() as i32,
-                            ),
-                        ));
-                    }
-                }
-            }
-        }
-    }
-    let graph: DirectedCsrGraph<i32> = GraphBuilder::new().edges(edges).build();
-    let shortest_path = starts
+pub fn solve_day_12_challenge_2(heightmap: &Heightmap) -> anyhow::Result<Score> {
+    let end = find(heightmap, 'E')?;
+    let shortest_path = heightmap
         .iter()
-        .filter_map(|start| {
-            let end = end.expect("end");
-            let start = reindex(start.0, start.1, heightmap.ncols() as i32);
-            let end = reindex(end.0, end.1, heightmap.ncols() as i32);
-            astar(
-                &start,
-                |&index| {
-                    graph
-                        .out_neighbors(index)
-                        .map(|p| (*p, 1))
-                        .collect::<Vec<(i32, u32)>>()
-                },
-                |p| {
-                    let (rp, cp) = deindex(*p, heightmap.ncols() as i32);
-                    let (ro, co) = deindex(end, heightmap.ncols() as i32);
-                    (rp.abs_diff(ro) + cp.abs_diff(co)) as u32
-                },
-                |p| *p == end,
-            )
-        })
+        .filter(|(_pos, c)| **c == 'S' || **c == 'a')
+        .filter_map(|(start, _c)| shortest_path(heightmap, start, end))
         .map(|(_path, cost)| cost)
         .min();
 
@@ -215,3 +81,21 @@ pub(crate) fn solve_day_12_challenge_2(input_path: &Path) -> anyhow::Result<Scor
         Err(anyhow!("no path found"))
     }
 }
+
+pub struct HillClimbingAlgorithm;
+
+impl Solver for HillClimbingAlgorithm {
+    type Input = Heightmap;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_12_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_12_challenge_2(input)?.into())
+    }
+}
@@ -0,0 +1,207 @@
+use crate::parser_utils::{parse_all, parse_blocks, positive_number};
+use crate::solver::{Answer, Solver};
+use nom::branch::alt;
+use nom::character::complete::{char, line_ending};
+use nom::combinator::map;
+use nom::multi::separated_list0;
+use nom::sequence::{delimited, separated_pair};
+use nom::IResult;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+type Score = usize;
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Int(usize),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(left), Packet::Int(right)) => left.cmp(right),
+            // lists are compared element-wise, then by length
+            (Packet::List(left), Packet::List(right)) => left.cmp(right),
+            // an integer compared to a list is first converted to a single element list
+            (Packet::Int(_), Packet::List(right)) => {
+                std::slice::from_ref(self).cmp(right.as_slice())
+            }
+            (Packet::List(left), Packet::Int(_)) => {
+                left.as_slice().cmp(std::slice::from_ref(other))
+            }
+        }
+    }
+}
+
+// consistent with the ordering, so that `3` and `[[3]]` are equal
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Int(value) => write!(f, "{}", value),
+            Packet::List(packets) => {
+                write!(f, "[")?;
+                for (index, packet) in packets.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", packet)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn parse_packet(input: &str) -> IResult<&str, Packet> {
+    alt((
+        map(positive_number, Packet::Int),
+        map(
+            delimited(
+                char('['),
+                separated_list0(char(','), parse_packet),
+                char(']'),
+            ),
+            Packet::List,
+        ),
+    ))(input)
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (Packet, Packet)> {
+    separated_pair(parse_packet, line_ending, parse_packet)(input)
+}
+
+// pairs are separated by a blank line
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<(Packet, Packet)>> {
+    Ok(parse_blocks(input, |block| {
+        parse_all(block, "a pair of packets", parse_pair)
+    })?)
+}
+
+pub fn solve_day_13_challenge_1(pairs: &[(Packet, Packet)]) -> Score {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left < right)
+        .map(|(index, _)| index + 1) // pairs are numbered from 1
+        .sum()
+}
+
+pub fn solve_day_13_challenge_2(pairs: &[(Packet, Packet)]) -> Score {
+    let dividers = [
+        Packet::List(vec![Packet::List(vec![Packet::Int(2)])]),
+        Packet::List(vec![Packet::List(vec![Packet::Int(6)])]),
+    ];
+    let mut packets: Vec<&Packet> = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .chain(dividers.iter())
+        .collect();
+    packets.sort_unstable();
+    dividers
+        .iter()
+        .filter_map(|divider| packets.binary_search(&divider).ok())
+        .map(|index| index + 1) // packets are numbered from 1
+        .product()
+}
+
+pub struct DistressSignal;
+
+impl Solver for DistressSignal {
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_13_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_13_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_13::{
+        parse_input, parse_packet, solve_day_13_challenge_1, solve_day_13_challenge_2, Packet,
+    };
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    fn packet(input: &str) -> Packet {
+        parse_packet(input).unwrap().1
+    }
+
+    #[test]
+    fn test_parse_packet() {
+        assert_eq!(
+            parse_packet("[1,[],[23]]"),
+            Ok((
+                "",
+                Packet::List(vec![
+                    Packet::Int(1),
+                    Packet::List(vec![]),
+                    Packet::List(vec![Packet::Int(23)]),
+                ])
+            ))
+        );
+        assert_eq!(packet("[[1],4]").to_string(), "[[1],4]");
+    }
+
+    #[test]
+    fn test_compare_mixed_packets() {
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert_eq!(packet("[[[3]]]"), packet("3"));
+        assert!(packet("[3]") < packet("[[3],0]"));
+        assert!(packet("[[]]") < packet("[0]"));
+    }
+
+    #[test]
+    fn test_solve_day_13_example() {
+        let pairs = parse_input(EXAMPLE).unwrap();
+        assert_eq!(pairs.len(), 8);
+        assert_eq!(solve_day_13_challenge_1(&pairs), 13);
+        assert_eq!(solve_day_13_challenge_2(&pairs), 140);
+    }
+}
@@ -1,15 +1,11 @@
-use crate::parser_utils::positive_number;
+use crate::grid::{Grid, Pos};
+use crate::parser_utils::{arrow_separated, comma, parse_lines, positive_number};
+use crate::solver::{Answer, Solver};
 use itertools::Itertools;
 use log::debug;
-use nalgebra::{min, DMatrix};
-use nom::bytes::complete::tag;
-use nom::character::complete::char;
-use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 use nom::IResult;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::fmt::{Display, Formatter};
 
 type Score = u64;
 
@@ -20,17 +16,18 @@ struct Point {
 }
 
 fn parse_point(input: &str) -> IResult<&str, Point> {
-    let (i, (x, y)) = separated_pair(positive_number, char(','), positive_number)(input)?;
+    let (i, (x, y)) = separated_pair(positive_number, comma, positive_number)(input)?;
     Ok((i, Point { x, y }))
 }
 
 fn parse_segments(input: &str) -> IResult<&str, Vec<Point>> {
-    separated_list1(tag(" -> "), parse_point)(input)
+    arrow_separated(parse_point)(input)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solve_14::{parse_segments, Point};
+    use crate::parser_utils::ParseErrors;
+    use crate::solve_14::{parse_input, parse_segments, solve_day_14_challenge_1, Point};
 
     #[test]
     fn test_parse_segments() {
@@ -39,27 +36,49 @@ mod tests {
             Ok(("", vec![Point { x: 42, y: 43 }, Point { x: 44, y: 45 }]))
         );
     }
+
+    #[test]
+    fn test_parse_input_malformed_line() {
+        let error = parse_input("498,4 -> 498,6\n503,4 => 502,4\n").unwrap_err();
+        let errors = error.downcast::<ParseErrors>().unwrap();
+        assert_eq!((errors.0[0].line, errors.0[0].column), (2, 6));
+        assert_eq!(errors.0[0].found, " => 502,4");
+    }
+
+    #[test]
+    fn test_sand_blocking_the_source() {
+        let rock_structure = parse_input("499,1 -> 501,1\n").unwrap();
+        assert_eq!(solve_day_14_challenge_1(&rock_structure), 1);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tile::Air => write!(f, "."),
+            Tile::Rock => write!(f, "#"),
+            Tile::Sand => write!(f, "o"),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct RockStructure {
-    rocks: DMatrix<u32>,
+    rock_segments: Vec<Vec<Point>>,
     min_x: usize,
     max_x: usize,
-    min_y: usize,
     max_y: usize,
 }
 
-pub(crate) fn parse_input(input_path: &Path) -> anyhow::Result<RockStructure> {
-    let input_file = File::open(input_path)?;
-    let buffer_reader = BufReader::new(input_file);
-    let mut rock_segments: Vec<Vec<Point>> = vec![];
-    for line in buffer_reader.lines() {
-        let line = line?;
-        if let Ok((_i, segments)) = parse_segments(line.as_str()) {
-            rock_segments.push(segments);
-        }
-    }
+pub fn parse_input(input: &str) -> anyhow::Result<RockStructure> {
+    let rock_segments = parse_lines(input, "points separated by ->", parse_segments)?;
     let min_x = rock_segments
         .iter()
         .flatten()
@@ -71,204 +90,131 @@ pub(crate) fn parse_input(input_path: &Path) -> anyhow::Result<RockStructure> {
         .flatten()
         .map(|p| p.x)
         .max()
-        .ok_or(anyhow::anyhow!("could not compute max for y"))?;
-    let min_y = rock_segments
-        .iter()
-        .flatten()
-        .map(|p| p.y)
-        .min()
-        .ok_or(anyhow::anyhow!("could not compute min for x"))?;
+        .ok_or(anyhow::anyhow!("could not compute max for x"))?;
     let max_y = rock_segments
         .iter()
         .flatten()
         .map(|p| p.y)
         .max()
         .ok_or(anyhow::anyhow!("could not compute max for y"))?;
-    debug!("x:{} {} y:{} {}", min_x, max_x, min_y, max_y);
-    let rows = max_y + 1; // 0 -> max_y
-    let cols = max_x - min_x + 1; // 0 -> max_x - min_x
-    let mut rocks = DMatrix::zeros(rows, cols);
-    for segments in rock_segments {
-        for (p1, p2) in segments.iter().tuple_windows() {
-            if p1.x == p2.x {
-                for y in p1.y.min(p2.y)..=p1.y.max(p2.y) {
-                    rocks[(y, p1.x - min_x)] = 1;
-                }
-            }
-            if p1.y == p2.y {
-                for x in p1.x.min(p2.x)..=p1.x.max(p2.x) {
-                    rocks[(p1.y, x - min_x)] = 1;
-                }
-            }
-        }
-    }
-
-    for row in rocks.row_iter() {
-        debug!(
-            "{:?}",
-            row.iter().map(|x| if *x == 1 { '#' } else { '.' }).join("")
-        );
-    }
-
+    debug!("x:{} {} y:{}", min_x, max_x, max_y);
     Ok(RockStructure {
-        rocks,
+        rock_segments,
         min_x,
         max_x,
-        min_y,
         max_y,
     })
 }
 
-pub(crate) fn solve_day_14_challenge_1(input_path: &Path) -> anyhow::Result<Score> {
-    let mut rock_structure = parse_input(input_path)?;
+const SAND_SOURCE: Point = Point { x: 500, y: 0 };
 
-    let mut sand: Point = Point { x: 500, y: 0 };
-    let mut outside = false;
-    let mut score: Score = 0;
-    while !outside {
-        if sand.y + 1 > rock_structure.max_y {
-            outside = true;
-            break;
-        }
-        if rock_structure.rocks[(sand.y + 1, sand.x - rock_structure.min_x)] == 0 {
-            // empty, sand can move there
-            sand.y += 1;
-            continue;
-        }
-        // not empty below
-        // check left
-        if sand.x - rock_structure.min_x == 0 {
-            outside = true;
-            break;
-        }
-        // left not outside
-        // check diagonally left
-        if rock_structure.rocks[(sand.y + 1, sand.x - rock_structure.min_x - 1)] == 0 {
-            // empty, sand can move there
-            sand.y += 1;
-            sand.x -= 1;
-            continue;
-        }
-        // not empty diagonally left
-        // check right
-        if sand.x == rock_structure.max_x {
-            outside = true;
-            break;
-        }
-        // right not outside
-        // check diagonally right
-        if rock_structure.rocks[(sand.y + 1, sand.x - rock_structure.min_x + 1)] == 0 {
-            // empty, sand can move there
-            sand.y += 1;
-            sand.x += 1;
-            continue;
+// tiles from x = min_x and y = 0, sand falling out of the grid falls into the abyss
+struct Cave {
+    tiles: Grid<Tile>,
+    min_x: usize,
+}
+
+impl Cave {
+    fn new(rock_structure: &RockStructure, min_x: usize, max_x: usize, max_y: usize) -> Cave {
+        let mut cave = Cave {
+            tiles: Grid::new(max_y + 1, max_x - min_x + 1, Tile::Air),
+            min_x,
+        };
+        for segments in rock_structure.rock_segments.iter() {
+            for (p1, p2) in segments.iter().tuple_windows() {
+                for y in p1.y.min(p2.y)..=p1.y.max(p2.y) {
+                    for x in p1.x.min(p2.x)..=p1.x.max(p2.x) {
+                        let pos = cave.pos(&Point { x, y });
+                        cave.tiles[pos] = Tile::Rock;
+                    }
+                }
+            }
         }
+        cave
+    }
 
-        // could not move bottom, diagonally left or diagonally right -> rest
-        rock_structure.rocks[(sand.y, sand.x - rock_structure.min_x)] = 2;
-        // debug!("resting here: {:?}", sand);
-        score += 1;
-        sand = Point { x: 500, y: 0 };
+    fn pos(&self, point: &Point) -> Pos {
+        Pos::new(point.y, point.x - self.min_x)
     }
 
-    for row in rock_structure.rocks.row_iter() {
-        debug!(
-            "{:?}",
-            row.iter()
-                .map(|x| match x {
-                    0 => '.',
-                    1 => '#',
-                    2 => 'o',
-                    _ => '?',
-                })
-                .join("")
-        );
+    // position where the sand comes to rest, none if it falls into the abyss
+    fn pour_sand(&mut self) -> Option<Pos> {
+        let mut sand = self.pos(&SAND_SOURCE);
+        'falling: loop {
+            // below, diagonally left, diagonally right
+            for dcol in [0, -1, 1] {
+                let next = self.tiles.offset(sand, 1, dcol)?;
+                if self.tiles[next] == Tile::Air {
+                    sand = next;
+                    continue 'falling;
+                }
+            }
+            self.tiles[sand] = Tile::Sand;
+            return Some(sand);
+        }
     }
-    Ok(score)
 }
 
-pub(crate) fn solve_day_14_challenge_2(input_path: &Path) -> anyhow::Result<Score> {
-    let rock_structure = parse_input(input_path)?;
-
-    let rows = rock_structure.rocks.nrows();
-    let cols = rock_structure.rocks.ncols();
-
-    let margin = rows + 2;
-    let mut rocks_challenge_2 = DMatrix::zeros(rows + 2, margin + cols + margin);
-    for r in 0..rock_structure.rocks.nrows() {
-        for c in 0..rock_structure.rocks.ncols() {
-            rocks_challenge_2[(r, c + margin)] = rock_structure.rocks[(r, c)];
+pub fn solve_day_14_challenge_1(rock_structure: &RockStructure) -> Score {
+    let mut cave = Cave::new(
+        rock_structure,
+        rock_structure.min_x.min(SAND_SOURCE.x),
+        rock_structure.max_x.max(SAND_SOURCE.x),
+        rock_structure.max_y,
+    );
+    let source = cave.pos(&SAND_SOURCE);
+    let mut score: Score = 0;
+    // sand may pile up to the source before any of it falls into the abyss
+    while let Some(sand) = cave.pour_sand() {
+        score += 1;
+        if sand == source {
+            break;
         }
     }
+    debug!("\n{}", cave.tiles);
+    score
+}
 
-    let last_row = rocks_challenge_2.nrows() - 1;
-    for c in 0..rocks_challenge_2.ncols() {
-        rocks_challenge_2[(last_row, c)] = 1;
+pub fn solve_day_14_challenge_2(rock_structure: &RockStructure) -> Score {
+    // sand spreads at most one column per row on each side of the source
+    let floor_y = rock_structure.max_y + 2;
+    let mut cave = Cave::new(
+        rock_structure,
+        rock_structure
+            .min_x
+            .min(SAND_SOURCE.x.saturating_sub(floor_y + 1)),
+        rock_structure.max_x.max(SAND_SOURCE.x + floor_y + 1),
+        floor_y,
+    );
+    for col in 0..cave.tiles.ncols() {
+        cave.tiles[Pos::new(floor_y, col)] = Tile::Rock;
     }
-
-    let mut sand: Point = Point { x: 500, y: 0 };
+    let source = cave.pos(&SAND_SOURCE);
     let mut score: Score = 0;
-    loop {
-        // debug!("sand: {:?}, margin {:?}", sand, margin);
-        if rocks_challenge_2[(sand.y + 1, sand.x + margin - rock_structure.min_x)] == 0 {
-            // empty, sand can move there
-            sand.y += 1;
-            continue;
-        }
-        // not empty below
-        // check left
-        if sand.x == rock_structure.min_x - margin {
-            debug!("should not happen thanks to margins");
-            break;
-        }
-        // left not outside
-        // check diagonally left
-        if rocks_challenge_2[(sand.y + 1, sand.x + margin - rock_structure.min_x - 1)] == 0 {
-            // empty, sand can move there
-            sand.y += 1;
-            sand.x -= 1;
-            continue;
-        }
-        // not empty diagonally left
-        // check right
-        if sand.x == rock_structure.max_x + margin {
-            debug!("should not happen thanks to margins");
-            break;
-        }
-        // right not outside
-        // check diagonally right
-        if rocks_challenge_2[(sand.y + 1, sand.x + margin - rock_structure.min_x + 1)] == 0 {
-            // empty, sand can move there
-            sand.y += 1;
-            sand.x += 1;
-            continue;
-        }
-        // check if we reached the top row
-        if sand.y == 0 {
-            rocks_challenge_2[(sand.y, sand.x + margin - rock_structure.min_x)] = 2;
-            score += 1;
+    while let Some(sand) = cave.pour_sand() {
+        score += 1;
+        if sand == source {
             break;
         }
+    }
+    debug!("\n{}", cave.tiles);
+    score
+}
 
-        // could not move bottom, diagonally left or diagonally right -> rest
-        rocks_challenge_2[(sand.y, sand.x + margin - rock_structure.min_x)] = 2;
-        // debug!("resting here: {:?}", sand);
-        score += 1;
-        sand = Point { x: 500, y: 0 };
+pub struct RegolithReservoir;
+
+impl Solver for RegolithReservoir {
+    type Input = RockStructure;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
     }
 
-    for row in rocks_challenge_2.row_iter() {
-        debug!(
-            "{:?}",
-            row.iter()
-                .map(|x| match x {
-                    0 => '.',
-                    1 => '#',
-                    2 => 'o',
-                    _ => '?',
-                })
-                .join("")
-        );
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_14_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_14_challenge_2(input).into())
     }
-    Ok(score)
 }
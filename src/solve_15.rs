@@ -0,0 +1,200 @@
+use crate::parser_utils::{comma, parse_lines, signed_number};
+use crate::solver::{Answer, Solver};
+use log::debug;
+use nalgebra::Point2;
+use nom::bytes::complete::tag;
+use nom::sequence::{preceded, separated_pair, tuple};
+use nom::IResult;
+use std::collections::HashSet;
+
+type Score = i64;
+
+#[derive(Debug, PartialEq)]
+pub struct Sensor {
+    position: Point2<i64>,
+    beacon: Point2<i64>,
+}
+
+impl Sensor {
+    fn range(&self) -> i64 {
+        manhattan_distance(&self.position, &self.beacon)
+    }
+
+    // positions of the row within range of the sensor, none if the row is out of range
+    fn coverage(&self, y: i64) -> Option<(i64, i64)> {
+        let half_width = self.range() - (self.position.y - y).abs();
+        if half_width < 0 {
+            None
+        } else {
+            Some((self.position.x - half_width, self.position.x + half_width))
+        }
+    }
+}
+
+fn manhattan_distance(a: &Point2<i64>, b: &Point2<i64>) -> i64 {
+    (a.x - b.x).abs() + (a.y - b.y).abs()
+}
+
+fn parse_point(input: &str) -> IResult<&str, Point2<i64>> {
+    let (i, (x, y)) = separated_pair(
+        preceded(tag("x="), signed_number),
+        comma,
+        preceded(tag("y="), signed_number),
+    )(input)?;
+    Ok((i, Point2::new(x, y)))
+}
+
+fn parse_sensor(input: &str) -> IResult<&str, Sensor> {
+    let (i, (_, position, _, beacon)) = tuple((
+        tag("Sensor at "),
+        parse_point,
+        tag(": closest beacon is at "),
+        parse_point,
+    ))(input)?;
+    Ok((i, Sensor { position, beacon }))
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Sensor>> {
+    Ok(parse_lines(
+        input,
+        "a sensor and its closest beacon",
+        parse_sensor,
+    )?)
+}
+
+// disjoint inclusive intervals sorted by start, adjacent intervals are merged
+fn merge(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    intervals.sort_unstable();
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end + 1 => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn row_coverage(sensors: &[Sensor], y: i64) -> Vec<(i64, i64)> {
+    merge(
+        sensors
+            .iter()
+            .filter_map(|sensor| sensor.coverage(y))
+            .collect(),
+    )
+}
+
+pub fn solve_day_15_challenge_1(sensors: &[Sensor], y: i64) -> Score {
+    let covered: i64 = row_coverage(sensors, y)
+        .iter()
+        .map(|(start, end)| end - start + 1)
+        .sum();
+    let beacons: HashSet<i64> = sensors
+        .iter()
+        .filter(|sensor| sensor.beacon.y == y)
+        .map(|sensor| sensor.beacon.x)
+        .collect();
+    covered - beacons.len() as i64
+}
+
+pub fn solve_day_15_challenge_2(sensors: &[Sensor], max: i64) -> Option<Score> {
+    for y in 0..=max {
+        // first position of the row after the covered positions starting from 0
+        let mut x = 0;
+        for (start, end) in row_coverage(sensors, y) {
+            if start > x {
+                break;
+            }
+            x = x.max(end + 1);
+        }
+        if x <= max {
+            debug!("distress beacon at x={}, y={}", x, y);
+            return Some(x * 4_000_000 + y);
+        }
+    }
+    None
+}
+
+pub struct BeaconExclusionZone {
+    pub row: i64,
+    pub max: i64,
+}
+
+impl Default for BeaconExclusionZone {
+    fn default() -> Self {
+        BeaconExclusionZone {
+            row: 2_000_000,
+            max: 4_000_000,
+        }
+    }
+}
+
+impl Solver for BeaconExclusionZone {
+    type Input = Vec<Sensor>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_15_challenge_1(input, self.row).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_15_challenge_2(input, self.max).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_15::{
+        merge, parse_input, parse_sensor, solve_day_15_challenge_1, solve_day_15_challenge_2,
+        Sensor,
+    };
+    use nalgebra::Point2;
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn test_parse_sensor() {
+        assert_eq!(
+            parse_sensor("Sensor at x=2, y=18: closest beacon is at x=-2, y=15"),
+            Ok((
+                "",
+                Sensor {
+                    position: Point2::new(2, 18),
+                    beacon: Point2::new(-2, 15)
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(
+            merge(vec![(5, 8), (-2, 2), (3, 4), (12, 14), (13, 13)]),
+            vec![(-2, 8), (12, 14)]
+        );
+    }
+
+    #[test]
+    fn test_solve_day_15_example() {
+        let sensors = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_15_challenge_1(&sensors, 10), 26);
+        assert_eq!(solve_day_15_challenge_2(&sensors, 20), Some(56000011));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::parser_utils::{comma_separated, parse_lines, positive_number};
+use crate::solver::{Answer, Solver};
+use anyhow::{anyhow, bail};
+use graph::prelude::{DirectedCsrGraph, DirectedNeighbors, Graph, GraphBuilder};
+use log::debug;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::alpha1;
+use nom::sequence::tuple;
+use nom::IResult;
+use pathfinding::prelude::dijkstra_all;
+use std::collections::HashMap;
+
+type Score = usize;
+
+const START: &str = "AA";
+
+#[derive(Debug, PartialEq)]
+struct Valve<'a> {
+    name: &'a str,
+    flow: usize,
+    tunnels: Vec<&'a str>,
+}
+
+fn parse_valve(input: &str) -> IResult<&str, Valve<'_>> {
+    let (i, (_, name, _, flow, _, tunnels)) = tuple((
+        tag("Valve "),
+        alpha1,
+        tag(" has flow rate="),
+        positive_number,
+        alt((
+            tag("; tunnels lead to valves "),
+            tag("; tunnel leads to valve "),
+        )),
+        comma_separated(alpha1),
+    ))(input)?;
+    Ok((
+        i,
+        Valve {
+            name,
+            flow,
+            tunnels,
+        },
+    ))
+}
+
+// the start valve and the valves with a non-zero flow, the only ones worth moving to
+#[derive(Debug)]
+pub struct Network {
+    flows: Vec<usize>,
+    // minutes to move from a valve to another, none if unreachable
+    distances: Vec<Vec<Option<usize>>>,
+    start: usize,
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Network> {
+    let valves = parse_lines(input, "a valve and its tunnels", parse_valve)?;
+    let indices: HashMap<&str, usize> = valves
+        .iter()
+        .enumerate()
+        .map(|(index, valve)| (valve.name, index))
+        .collect();
+    let mut edges: Vec<(usize, usize)> = vec![];
+    for (index, valve) in valves.iter().enumerate() {
+        for tunnel in valve.tunnels.iter() {
+            let target = indices
+                .get(tunnel)
+                .ok_or_else(|| anyhow!("valve {} leads to unknown valve {}", valve.name, tunnel))?;
+            edges.push((index, *target));
+        }
+    }
+    let graph: DirectedCsrGraph<usize> = GraphBuilder::new().edges(edges).build();
+
+    let relevant: Vec<usize> = valves
+        .iter()
+        .enumerate()
+        .filter(|(_, valve)| valve.flow > 0 || valve.name == START)
+        .map(|(index, _)| index)
+        .collect();
+    // opened valves are tracked in a u64 bitmask
+    if relevant.len() > 64 {
+        bail!("too many valves to track: {}", relevant.len());
+    }
+    let start = relevant
+        .iter()
+        .position(|index| valves[*index].name == START)
+        .ok_or_else(|| anyhow!("no valve {}", START))?;
+    let distances = relevant
+        .iter()
+        .map(|from| {
+            // valves without tunnels are not part of the graph
+            let reachable = if *from < graph.node_count() {
+                dijkstra_all(from, |valve| {
+                    graph.out_neighbors(*valve).map(|next| (*next, 1usize))
+                })
+            } else {
+                HashMap::new()
+            };
+            relevant
+                .iter()
+                .map(|to| {
+                    if to == from {
+                        Some(0)
+                    } else {
+                        reachable.get(to).map(|(_, distance)| *distance)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Ok(Network {
+        flows: relevant.iter().map(|index| valves[*index].flow).collect(),
+        distances,
+        start,
+    })
+}
+
+// records the most pressure released for each set of opened valves
+fn explore(
+    network: &Network,
+    valve: usize,
+    minutes: usize,
+    opened: u64,
+    pressure: Score,
+    best: &mut HashMap<u64, Score>,
+) {
+    let entry = best.entry(opened).or_insert(0);
+    *entry = (*entry).max(pressure);
+    for next in 0..network.flows.len() {
+        if network.flows[next] == 0 || opened & (1 << next) != 0 {
+            continue;
+        }
+        // moving there then opening the valve
+        if let Some(cost) = network.distances[valve][next].map(|distance| distance + 1) {
+            if cost < minutes {
+                let remaining = minutes - cost;
+                explore(
+                    network,
+                    next,
+                    remaining,
+                    opened | (1 << next),
+                    pressure + network.flows[next] * remaining,
+                    best,
+                );
+            }
+        }
+    }
+}
+
+fn best_pressures(network: &Network, minutes: usize) -> HashMap<u64, Score> {
+    let mut best = HashMap::new();
+    explore(network, network.start, minutes, 0, 0, &mut best);
+    debug!("{} sets of opened valves", best.len());
+    best
+}
+
+pub fn solve_day_16_challenge_1(network: &Network) -> Score {
+    best_pressures(network, 30)
+        .values()
+        .copied()
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn solve_day_16_challenge_2(network: &Network) -> Score {
+    // you and the elephant open disjoint sets of valves
+    let best: Vec<(u64, Score)> = best_pressures(network, 26).into_iter().collect();
+    let mut most = 0;
+    for (i, (mine, my_pressure)) in best.iter().enumerate() {
+        for (elephants, elephant_pressure) in best[i..].iter() {
+            if mine & elephants == 0 {
+                most = most.max(my_pressure + elephant_pressure);
+            }
+        }
+    }
+    most
+}
+
+pub struct ProboscideaVolcanium;
+
+impl Solver for ProboscideaVolcanium {
+    type Input = Network;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_16_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_16_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_16::{
+        parse_input, parse_valve, solve_day_16_challenge_1, solve_day_16_challenge_2, Valve,
+    };
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn test_parse_valve() {
+        assert_eq!(
+            parse_valve("Valve HH has flow rate=22; tunnel leads to valve GG"),
+            Ok((
+                "",
+                Valve {
+                    name: "HH",
+                    flow: 22,
+                    tunnels: vec!["GG"]
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve_day_16_example() {
+        let network = parse_input(EXAMPLE).unwrap();
+        assert_eq!(network.flows.len(), 7);
+        assert_eq!(network.distances[network.start][5], Some(5)); // AA to HH
+        assert_eq!(solve_day_16_challenge_1(&network), 1651);
+        assert_eq!(solve_day_16_challenge_2(&network), 1707);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::parser_utils::ParseError;
+use crate::solver::{Answer, Solver};
+use anyhow::bail;
+use log::debug;
+use std::collections::HashMap;
+
+type Score = u64;
+
+const WIDTH: usize = 7;
+
+// one bit per column, the leftmost column is the most significant of the 7 bits
+type Row = u8;
+
+const LEFT_WALL: Row = 1 << (WIDTH - 1);
+const RIGHT_WALL: Row = 1;
+
+// rows from bottom to top, shifted so that the left edge is two units away from the left wall
+const ROCKS: [&[Row]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Jet>> {
+    let mut jets = vec![];
+    for (index, c) in input.trim_end().char_indices() {
+        match c {
+            '<' => jets.push(Jet::Left),
+            '>' => jets.push(Jet::Right),
+            _ => return Err(ParseError::at(input, &input[index..], "a jet, < or >").into()),
+        }
+    }
+    if jets.is_empty() {
+        bail!("no jet pattern");
+    }
+    Ok(jets)
+}
+
+struct Chamber<'a> {
+    jets: &'a [Jet],
+    // settled rocks from bottom to top, there is no empty row on top
+    rows: Vec<Row>,
+    rock_index: usize,
+    jet_index: usize,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a [Jet]) -> Chamber<'a> {
+        Chamber {
+            jets,
+            rows: vec![],
+            rock_index: 0,
+            jet_index: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn collides(&self, rock: &[Row], y: usize) -> bool {
+        rock.iter().enumerate().any(|(r, row)| {
+            self.rows
+                .get(y + r)
+                .is_some_and(|settled| settled & row != 0)
+        })
+    }
+
+    fn push(&self, rock: &[Row], y: usize, jet: Jet) -> Option<Vec<Row>> {
+        let pushed: Vec<Row> = match jet {
+            Jet::Left if rock.iter().all(|row| row & LEFT_WALL == 0) => {
+                rock.iter().map(|row| row << 1).collect()
+            }
+            Jet::Right if rock.iter().all(|row| row & RIGHT_WALL == 0) => {
+                rock.iter().map(|row| row >> 1).collect()
+            }
+            _ => return None,
+        };
+        if self.collides(&pushed, y) {
+            None
+        } else {
+            Some(pushed)
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rock_index].to_vec();
+        self.rock_index = (self.rock_index + 1) % ROCKS.len();
+        // bottom edge three units above the highest rock
+        let mut y = self.height() + 3;
+        loop {
+            let jet = self.jets[self.jet_index];
+            self.jet_index = (self.jet_index + 1) % self.jets.len();
+            if let Some(pushed) = self.push(&rock, y, jet) {
+                rock = pushed;
+            }
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (r, row) in rock.into_iter().enumerate() {
+            if y + r < self.rows.len() {
+                self.rows[y + r] |= row;
+            } else {
+                self.rows.push(row);
+            }
+        }
+    }
+
+    // depth of the highest rock of each column from the top of the tower
+    fn profile(&self) -> [usize; WIDTH] {
+        let mut profile = [self.height(); WIDTH];
+        for (column, depth) in profile.iter_mut().enumerate() {
+            let mask = LEFT_WALL >> column;
+            if let Some(d) = self.rows.iter().rev().position(|row| row & mask != 0) {
+                *depth = d;
+            }
+        }
+        profile
+    }
+}
+
+// the chamber state repeats eventually so the height is extrapolated from the first cycle
+fn tower_height(jets: &[Jet], rocks: u64) -> Score {
+    let mut chamber = Chamber::new(jets);
+    let mut seen: HashMap<(usize, usize, [usize; WIDTH]), (u64, usize)> = HashMap::new();
+    let mut dropped: u64 = 0;
+    // height of the cycles that were skipped rather than simulated
+    let mut skipped_height: Option<u64> = None;
+    while dropped < rocks {
+        chamber.drop_rock();
+        dropped += 1;
+        if skipped_height.is_some() {
+            continue;
+        }
+        let state = (chamber.rock_index, chamber.jet_index, chamber.profile());
+        if let Some((previous_dropped, previous_height)) =
+            seen.insert(state, (dropped, chamber.height()))
+        {
+            let cycle_rocks = dropped - previous_dropped;
+            let cycle_height = (chamber.height() - previous_height) as u64;
+            let cycles = (rocks - dropped) / cycle_rocks;
+            debug!(
+                "cycle of {} rocks adding {} rows after {} rocks",
+                cycle_rocks, cycle_height, previous_dropped
+            );
+            dropped += cycles * cycle_rocks;
+            skipped_height = Some(cycles * cycle_height);
+        }
+    }
+    chamber.height() as u64 + skipped_height.unwrap_or(0)
+}
+
+pub fn solve_day_17_challenge_1(jets: &[Jet]) -> Score {
+    tower_height(jets, 2022)
+}
+
+pub fn solve_day_17_challenge_2(jets: &[Jet]) -> Score {
+    tower_height(jets, 1_000_000_000_000)
+}
+
+pub struct PyroclasticFlow;
+
+impl Solver for PyroclasticFlow {
+    type Input = Vec<Jet>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_17_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_17_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_17::{
+        parse_input, solve_day_17_challenge_1, solve_day_17_challenge_2, tower_height, Chamber,
+    };
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    #[test]
+    fn test_drop_rocks() {
+        let jets = parse_input(EXAMPLE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        chamber.drop_rock();
+        chamber.drop_rock();
+        assert_eq!(
+            chamber.rows,
+            vec![0b0011110, 0b0001000, 0b0011100, 0b0001000]
+        );
+        assert_eq!(chamber.profile(), [4, 4, 1, 0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_solve_day_17_example() {
+        let jets = parse_input(EXAMPLE).unwrap();
+        assert_eq!(tower_height(&jets, 10), 17);
+        assert_eq!(solve_day_17_challenge_1(&jets), 3068);
+        assert_eq!(solve_day_17_challenge_2(&jets), 1514285714288);
+    }
+}
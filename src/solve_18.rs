@@ -0,0 +1,135 @@
+use crate::parser_utils::{comma, parse_lines, signed_number};
+use crate::solver::{Answer, Solver};
+use log::debug;
+use nalgebra::{Point3, Vector3};
+use nom::sequence::tuple;
+use nom::IResult;
+use std::collections::HashSet;
+
+type Score = usize;
+
+type Cube = Point3<i64>;
+
+const FACES: [Vector3<i64>; 6] = [
+    Vector3::new(1, 0, 0),
+    Vector3::new(-1, 0, 0),
+    Vector3::new(0, 1, 0),
+    Vector3::new(0, -1, 0),
+    Vector3::new(0, 0, 1),
+    Vector3::new(0, 0, -1),
+];
+
+fn parse_cube(input: &str) -> IResult<&str, Cube> {
+    let (i, (x, _, y, _, z)) =
+        tuple((signed_number, comma, signed_number, comma, signed_number))(input)?;
+    Ok((i, Point3::new(x, y, z)))
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<HashSet<Cube>> {
+    let cubes = parse_lines(input, "x,y,z coordinates", parse_cube)?;
+    Ok(cubes.into_iter().collect())
+}
+
+fn adjacent(cube: &Cube) -> impl Iterator<Item = Cube> + '_ {
+    FACES.iter().map(move |face| cube + face)
+}
+
+pub fn solve_day_18_challenge_1(cubes: &HashSet<Cube>) -> Score {
+    cubes
+        .iter()
+        .flat_map(adjacent)
+        .filter(|neighbour| !cubes.contains(neighbour))
+        .count()
+}
+
+pub fn solve_day_18_challenge_2(cubes: &HashSet<Cube>) -> Score {
+    if cubes.is_empty() {
+        return 0;
+    }
+    // bounding box with a layer of air all around so that the outside is connected
+    let min = cubes
+        .iter()
+        .fold(Point3::new(i64::MAX, i64::MAX, i64::MAX), |min, cube| {
+            min.inf(cube)
+        })
+        - Vector3::new(1, 1, 1);
+    let max = cubes
+        .iter()
+        .fold(Point3::new(i64::MIN, i64::MIN, i64::MIN), |max, cube| {
+            max.sup(cube)
+        })
+        + Vector3::new(1, 1, 1);
+    let inside =
+        |cube: &Cube| (0..3).all(|axis| min[axis] <= cube[axis] && cube[axis] <= max[axis]);
+
+    // flood fill the outside air, counting faces of lava cubes along the way
+    let mut outside: HashSet<Cube> = HashSet::from([min]);
+    let mut stack: Vec<Cube> = vec![min];
+    let mut faces = 0;
+    while let Some(air) = stack.pop() {
+        for neighbour in adjacent(&air) {
+            if cubes.contains(&neighbour) {
+                faces += 1;
+            } else if inside(&neighbour) && outside.insert(neighbour) {
+                stack.push(neighbour);
+            }
+        }
+    }
+    debug!("{} outside air cubes", outside.len());
+    faces
+}
+
+pub struct BoilingBoulders;
+
+impl Solver for BoilingBoulders {
+    type Input = HashSet<Cube>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_18_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_18_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_18::{
+        parse_cube, parse_input, solve_day_18_challenge_1, solve_day_18_challenge_2,
+    };
+    use nalgebra::Point3;
+
+    const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn test_parse_cube() {
+        assert_eq!(parse_cube("1,-2,3"), Ok(("", Point3::new(1, -2, 3))));
+    }
+
+    #[test]
+    fn test_solve_day_18_example() {
+        let cubes = parse_input("1,1,1\n2,1,1\n").unwrap();
+        assert_eq!(solve_day_18_challenge_1(&cubes), 10);
+        let cubes = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_18_challenge_1(&cubes), 64);
+        assert_eq!(solve_day_18_challenge_2(&cubes), 58);
+    }
+}
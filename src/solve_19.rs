@@ -0,0 +1,244 @@
+use crate::parser_utils::{parse_all, unsigned};
+use crate::solver::{Answer, Solver};
+use log::debug;
+use nom::bytes::complete::tag;
+use nom::character::complete::multispace1;
+use nom::combinator::cut;
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, tuple};
+use nom::IResult;
+use std::thread;
+
+type Score = u64;
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+// costs[robot][resource]
+#[derive(Debug, PartialEq)]
+pub struct Blueprint {
+    id: u32,
+    costs: [[u32; 4]; 4],
+}
+
+impl Blueprint {
+    // a factory builds a single robot per minute so more robots than that cannot be spent
+    fn max_spend(&self) -> [u32; 4] {
+        let mut max_spend = [0, 0, 0, u32::MAX];
+        for costs in self.costs.iter() {
+            for resource in [ORE, CLAY, OBSIDIAN] {
+                max_spend[resource] = max_spend[resource].max(costs[resource]);
+            }
+        }
+        max_spend
+    }
+}
+
+fn parse_blueprint(input: &str) -> IResult<&str, Blueprint> {
+    let (i, id) = delimited(tag("Blueprint "), unsigned, tag(":"))(input)?;
+    // past the header a malformed cost is reported where it fails
+    let (i, (ore_robot, clay_robot, (obsidian_robot_ore, _, obsidian_robot_clay))) = cut(tuple((
+        delimited(
+            tuple((multispace1, tag("Each ore robot costs "))),
+            unsigned,
+            tag(" ore."),
+        ),
+        delimited(
+            tuple((multispace1, tag("Each clay robot costs "))),
+            unsigned,
+            tag(" ore."),
+        ),
+        delimited(
+            tuple((multispace1, tag("Each obsidian robot costs "))),
+            tuple((unsigned, tag(" ore and "), unsigned)),
+            tag(" clay."),
+        ),
+    )))(i)?;
+    let (i, (geode_robot_ore, _, geode_robot_obsidian)) = cut(delimited(
+        tuple((multispace1, tag("Each geode robot costs "))),
+        tuple((unsigned, tag(" ore and "), unsigned)),
+        tag(" obsidian."),
+    ))(i)?;
+    let costs = [
+        [ore_robot, 0, 0, 0],
+        [clay_robot, 0, 0, 0],
+        [obsidian_robot_ore, obsidian_robot_clay, 0, 0],
+        [geode_robot_ore, 0, geode_robot_obsidian, 0],
+    ];
+    Ok((i, Blueprint { id, costs }))
+}
+
+fn parse_blueprints(input: &str) -> IResult<&str, Vec<Blueprint>> {
+    separated_list1(multispace1, parse_blueprint)(input)
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Blueprint>> {
+    Ok(parse_all(input, "a blueprint", parse_blueprints)?)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes: u32,
+    resources: [u32; 4],
+    robots: [u32; 4],
+}
+
+impl State {
+    // the state right after building the robot, none if it cannot be built in time
+    fn build(&self, blueprint: &Blueprint, robot: usize) -> Option<State> {
+        let costs = &blueprint.costs[robot];
+        let mut wait = 0;
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            let missing = costs[resource].saturating_sub(self.resources[resource]);
+            if missing > 0 {
+                if self.robots[resource] == 0 {
+                    return None;
+                }
+                wait = wait.max(missing.div_ceil(self.robots[resource]));
+            }
+        }
+        // a robot built during the last minute collects nothing
+        let elapsed = wait + 1;
+        if elapsed >= self.minutes {
+            return None;
+        }
+        let mut next = *self;
+        next.minutes -= elapsed;
+        for resource in [ORE, CLAY, OBSIDIAN, GEODE] {
+            next.resources[resource] += self.robots[resource] * elapsed;
+            next.resources[resource] -= costs[resource];
+        }
+        next.robots[robot] += 1;
+        Some(next)
+    }
+
+    // geodes when building no more robots
+    fn geodes(&self) -> u32 {
+        self.resources[GEODE] + self.robots[GEODE] * self.minutes
+    }
+
+    // geodes when building a geode robot every remaining minute
+    fn upper_bound(&self) -> u32 {
+        self.geodes() + self.minutes * self.minutes.saturating_sub(1) / 2
+    }
+}
+
+// depth first over the next robot to build, skipping the minutes spent waiting for it
+fn search(blueprint: &Blueprint, max_spend: &[u32; 4], state: State, best: &mut u32) {
+    *best = (*best).max(state.geodes());
+    if state.upper_bound() <= *best {
+        return;
+    }
+    for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+        if state.robots[robot] >= max_spend[robot] {
+            continue;
+        }
+        if let Some(next) = state.build(blueprint, robot) {
+            search(blueprint, max_spend, next, best);
+        }
+    }
+}
+
+fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let state = State {
+        minutes,
+        resources: [0; 4],
+        robots: [1, 0, 0, 0],
+    };
+    let mut best = 0;
+    search(blueprint, &blueprint.max_spend(), state, &mut best);
+    debug!("blueprint {}: {} geodes", blueprint.id, best);
+    best
+}
+
+// blueprints are independent, one thread each
+fn max_geodes_in_parallel(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(move || max_geodes(blueprint, minutes)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("blueprint search panicked"))
+            .collect()
+    })
+}
+
+pub fn solve_day_19_challenge_1(blueprints: &[Blueprint]) -> Score {
+    blueprints
+        .iter()
+        .zip(max_geodes_in_parallel(blueprints, 24))
+        .map(|(blueprint, geodes)| (blueprint.id * geodes) as Score)
+        .sum()
+}
+
+pub fn solve_day_19_challenge_2(blueprints: &[Blueprint]) -> Score {
+    let first = &blueprints[..blueprints.len().min(3)];
+    max_geodes_in_parallel(first, 32)
+        .iter()
+        .map(|geodes| *geodes as Score)
+        .product()
+}
+
+pub struct NotEnoughMinerals;
+
+impl Solver for NotEnoughMinerals {
+    type Input = Vec<Blueprint>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_19_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_19_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_19::{max_geodes, parse_input, solve_day_19_challenge_1, Blueprint};
+
+    const EXAMPLE: &str = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn test_parse_blueprint() {
+        let blueprints = parse_input(
+            "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.\n",
+        )
+        .unwrap();
+        assert_eq!(
+            blueprints,
+            vec![Blueprint {
+                id: 1,
+                costs: [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]]
+            }]
+        );
+        assert_eq!(parse_input(EXAMPLE).unwrap()[0], blueprints[0]);
+    }
+
+    #[test]
+    fn test_solve_day_19_example() {
+        let blueprints = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_19_challenge_1(&blueprints), 33);
+        assert_eq!(max_geodes(&blueprints[0], 32), 56);
+        assert_eq!(max_geodes(&blueprints[1], 32), 62);
+    }
+}
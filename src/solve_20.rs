@@ -0,0 +1,196 @@
+use crate::parser_utils::{parse_lines, signed_number};
+use crate::solver::{Answer, Solver};
+use anyhow::bail;
+
+type Score = i64;
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<i64>> {
+    let numbers = parse_lines(input, "a number", signed_number)?;
+    let zeros = numbers.iter().filter(|number| **number == 0).count();
+    if zeros != 1 {
+        bail!("expected a single 0, found {}", zeros);
+    }
+    Ok(numbers)
+}
+
+// items 0..len split into blocks of about sqrt(len) items, moving an item costs O(sqrt(len))
+struct BlockedList {
+    blocks: Vec<Vec<usize>>,
+    // block currently holding each item
+    block_of: Vec<usize>,
+    block_size: usize,
+}
+
+impl BlockedList {
+    fn new(len: usize) -> BlockedList {
+        let mut list = BlockedList {
+            blocks: vec![(0..len).collect()],
+            block_of: vec![0; len],
+            block_size: (len as f64).sqrt().ceil().max(1.0) as usize,
+        };
+        list.rebuild();
+        list
+    }
+
+    // evens out the blocks once one of them grew too large
+    fn rebuild(&mut self) {
+        let items: Vec<usize> = self.iter().collect();
+        self.blocks = items
+            .chunks(self.block_size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        for (b, block) in self.blocks.iter().enumerate() {
+            for item in block.iter() {
+                self.block_of[*item] = b;
+            }
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().flatten().copied()
+    }
+
+    // position of the item before it was removed
+    fn remove(&mut self, item: usize) -> usize {
+        let b = self.block_of[item];
+        let offset = self.blocks[b]
+            .iter()
+            .position(|i| *i == item)
+            .expect("item in its block");
+        self.blocks[b].remove(offset);
+        self.blocks[..b]
+            .iter()
+            .map(|block| block.len())
+            .sum::<usize>()
+            + offset
+    }
+
+    fn insert(&mut self, position: usize, item: usize) {
+        let mut offset = position;
+        let mut b = 0;
+        // the last block takes whatever is left
+        while b + 1 < self.blocks.len() && offset > self.blocks[b].len() {
+            offset -= self.blocks[b].len();
+            b += 1;
+        }
+        self.blocks[b].insert(offset, item);
+        self.block_of[item] = b;
+        if self.blocks[b].len() > 2 * self.block_size {
+            self.rebuild();
+        }
+    }
+}
+
+// numbers are identified by their original index so duplicated values move independently
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let len = numbers.len();
+    let mut list = BlockedList::new(len);
+    if len > 1 {
+        for _ in 0..rounds {
+            for (item, number) in numbers.iter().enumerate() {
+                let position = list.remove(item) as i64;
+                // the moving number is not part of the list while moving around it
+                let new_position = (position + number).rem_euclid(len as i64 - 1);
+                list.insert(new_position as usize, item);
+            }
+        }
+    }
+    list.iter().map(|item| numbers[item]).collect()
+}
+
+fn grove_coordinates(mixed: &[i64]) -> Score {
+    let zero = mixed
+        .iter()
+        .position(|number| *number == 0)
+        .expect("a single 0 checked when parsing");
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+pub fn solve_day_20_challenge_1(numbers: &[i64]) -> Score {
+    grove_coordinates(&mix(numbers, 1))
+}
+
+pub fn solve_day_20_challenge_2(numbers: &[i64]) -> Score {
+    let decrypted: Vec<i64> = numbers
+        .iter()
+        .map(|number| number * DECRYPTION_KEY)
+        .collect();
+    grove_coordinates(&mix(&decrypted, 10))
+}
+
+pub struct GrovePositioningSystem;
+
+impl Solver for GrovePositioningSystem {
+    type Input = Vec<i64>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_20_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_20_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_20::{mix, parse_input, solve_day_20_challenge_1, solve_day_20_challenge_2};
+
+    const EXAMPLE: &str = "1\n2\n-3\n3\n-2\n0\n4\n";
+
+    // the list is circular so compare from the first number
+    fn rotated(mut mixed: Vec<i64>, first: i64) -> Vec<i64> {
+        let start = mixed.iter().position(|number| *number == first).unwrap();
+        mixed.rotate_left(start);
+        mixed
+    }
+
+    #[test]
+    fn test_mix() {
+        let numbers = parse_input(EXAMPLE).unwrap();
+        assert_eq!(rotated(mix(&numbers, 1), 1), vec![1, 2, -3, 4, 0, 3, -2]);
+    }
+
+    // plain vec remove and insert
+    fn naive_mix(numbers: &[i64]) -> Vec<i64> {
+        let mut items: Vec<usize> = (0..numbers.len()).collect();
+        for (item, number) in numbers.iter().enumerate() {
+            let position = items.iter().position(|i| *i == item).unwrap();
+            items.remove(position);
+            let new_position = (position as i64 + number).rem_euclid(items.len() as i64);
+            items.insert(new_position as usize, item);
+        }
+        items.iter().map(|item| numbers[*item]).collect()
+    }
+
+    #[test]
+    fn test_mix_duplicates() {
+        assert_eq!(rotated(mix(&[1, 1, 0, -1], 1), 0), vec![0, 1, 1, -1]);
+        // enough numbers for several blocks, with many duplicates and moves across blocks
+        let mut numbers: Vec<i64> = (1..500)
+            .map(|i| (i * 37 % 23) - 11)
+            .map(|n| if n == 0 { 5 } else { n * 13 })
+            .collect();
+        numbers.insert(250, 0);
+        assert_eq!(
+            rotated(mix(&numbers, 1), 0),
+            rotated(naive_mix(&numbers), 0)
+        );
+    }
+
+    #[test]
+    fn test_solve_day_20_example() {
+        let numbers = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_20_challenge_1(&numbers), 3);
+        assert_eq!(solve_day_20_challenge_2(&numbers), 1623178306);
+    }
+}
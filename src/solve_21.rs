@@ -0,0 +1,380 @@
+use crate::math_utils::gcd;
+use crate::parser_utils::{parse_lines, unsigned, ParseError, ParseErrors};
+use crate::solver::{Answer, Solver};
+use anyhow::{anyhow, bail};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, char, one_of};
+use nom::combinator::map;
+use nom::sequence::{separated_pair, tuple};
+use nom::IResult;
+use pathfinding::prelude::topological_sort;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+type Score = i64;
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+// reduced fraction with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Rational {
+    // none when dividing by zero
+    fn new(numerator: i64, denominator: i64) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Some(Rational {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    fn integer(value: i64) -> Rational {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    // none on overflow or when dividing by zero
+    fn apply(&self, operator: Operator, other: &Rational) -> Option<Rational> {
+        let (a, b) = (self.numerator, self.denominator);
+        let (c, d) = (other.numerator, other.denominator);
+        match operator {
+            Operator::Add => Rational::new(
+                a.checked_mul(d)?.checked_add(c.checked_mul(b)?)?,
+                b.checked_mul(d)?,
+            ),
+            Operator::Sub => Rational::new(
+                a.checked_mul(d)?.checked_sub(c.checked_mul(b)?)?,
+                b.checked_mul(d)?,
+            ),
+            Operator::Mul => Rational::new(a.checked_mul(c)?, b.checked_mul(d)?),
+            Operator::Div => Rational::new(a.checked_mul(d)?, b.checked_mul(c)?),
+        }
+    }
+
+    fn to_integer(self) -> anyhow::Result<i64> {
+        if self.denominator != 1 {
+            bail!("{} is not an integer", self);
+        }
+        Ok(self.numerator)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Job {
+    Number(i64),
+    Operation(String, Operator, String),
+}
+
+fn parse_operator(input: &str) -> IResult<&str, Operator> {
+    map(one_of("+-*/"), |operator| match operator {
+        '+' => Operator::Add,
+        '-' => Operator::Sub,
+        '*' => Operator::Mul,
+        '/' => Operator::Div,
+        _ => unreachable!(), // because we match against + - * / using nom
+    })(input)
+}
+
+fn parse_job(input: &str) -> IResult<&str, Job> {
+    alt((
+        map(unsigned, Job::Number),
+        map(
+            tuple((alpha1, char(' '), parse_operator, char(' '), alpha1)),
+            |(left, _, operator, _, right)| {
+                Job::Operation(left.to_string(), operator, right.to_string())
+            },
+        ),
+    ))(input)
+}
+
+fn parse_monkey(input: &str) -> IResult<&str, (&str, Job)> {
+    separated_pair(alpha1, tag(": "), parse_job)(input)
+}
+
+// jobs of monkeys that do not wait for themselves
+#[derive(Debug)]
+pub struct Monkeys {
+    jobs: HashMap<String, Job>,
+    // each monkey comes after the monkeys it waits for
+    order: Vec<String>,
+}
+
+// what each monkey yells, none when its operation overflows or divides by zero
+type Yells<'a> = HashMap<&'a str, Option<Rational>>;
+
+impl Monkeys {
+    fn job(&self, name: &str) -> anyhow::Result<&Job> {
+        self.jobs
+            .get(name)
+            .ok_or_else(|| anyhow!("no monkey {}", name))
+    }
+
+    fn yells(&self) -> Yells<'_> {
+        let mut yells: Yells = HashMap::new();
+        for name in &self.order {
+            let yell = match &self.jobs[name] {
+                Job::Number(number) => Some(Rational::integer(*number)),
+                Job::Operation(left, operator, right) => {
+                    match (yells[left.as_str()], yells[right.as_str()]) {
+                        (Some(left), Some(right)) => left.apply(*operator, &right),
+                        _ => None,
+                    }
+                }
+            };
+            yells.insert(name, yell);
+        }
+        yells
+    }
+
+    // the human and the monkeys waiting for it, directly or not
+    fn depending_on_human(&self) -> HashSet<&str> {
+        let mut depending: HashSet<&str> = HashSet::new();
+        for name in &self.order {
+            let depends = match &self.jobs[name] {
+                _ if name == HUMAN => true,
+                Job::Number(_) => false,
+                Job::Operation(left, _, right) => {
+                    depending.contains(left.as_str()) || depending.contains(right.as_str())
+                }
+            };
+            if depends {
+                depending.insert(name);
+            }
+        }
+        depending
+    }
+}
+
+fn yell(yells: &Yells, name: &str) -> anyhow::Result<Rational> {
+    match yells.get(name) {
+        Some(Some(yell)) => Ok(*yell),
+        Some(None) => bail!("monkey {} cannot compute its number", name),
+        None => bail!("no monkey {}", name),
+    }
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Monkeys> {
+    let monkeys = parse_lines(input, "a monkey and its job", parse_monkey)?;
+    let names: Vec<&str> = monkeys.iter().map(|(name, _)| *name).collect();
+    let known: HashSet<&str> = names.iter().copied().collect();
+    let mut errors: Vec<ParseError> = vec![];
+    let mut jobs: HashMap<String, Job> = HashMap::new();
+    for (name, job) in &monkeys {
+        if jobs.contains_key(*name) {
+            errors.push(ParseError::at(input, name, "a monkey not seen before"));
+        }
+        if let Job::Operation(left, _, right) = job {
+            for operand in [left, right] {
+                if !known.contains(operand.as_str()) {
+                    errors.push(ParseError::at(
+                        input,
+                        name,
+                        &format!("a job on existing monkeys, no monkey {}", operand),
+                    ));
+                }
+            }
+        }
+        jobs.insert(name.to_string(), job.clone());
+    }
+    if !errors.is_empty() {
+        return Err(ParseErrors(errors).into());
+    }
+    let mut order = topological_sort(&names, |name| match &jobs[*name] {
+        Job::Number(_) => vec![],
+        Job::Operation(left, _, right) => vec![left.as_str(), right.as_str()],
+    })
+    .map_err(|cycle| {
+        // located at the line of the monkey rather than where it is waited for
+        let name = names.iter().find(|name| **name == cycle).unwrap_or(&cycle);
+        ParseError::at(input, name, "a monkey that does not wait for itself")
+    })?;
+    order.reverse();
+    let order = order.into_iter().map(String::from).collect();
+    Ok(Monkeys { jobs, order })
+}
+
+pub fn solve_day_21_challenge_1(monkeys: &Monkeys) -> anyhow::Result<Score> {
+    yell(&monkeys.yells(), ROOT)?.to_integer()
+}
+
+pub fn solve_day_21_challenge_2(monkeys: &Monkeys) -> anyhow::Result<Score> {
+    let (left, right) = match monkeys.job(ROOT)? {
+        Job::Operation(left, _, right) => (left.as_str(), right.as_str()),
+        Job::Number(_) => bail!("{} does not compare two monkeys", ROOT),
+    };
+    let yells = monkeys.yells();
+    let depending = monkeys.depending_on_human();
+    // the value that the monkey depending on the human must yell
+    let (mut name, mut target) = match (depending.contains(left), depending.contains(right)) {
+        (true, false) => (left, yell(&yells, right)?),
+        (false, true) => (right, yell(&yells, left)?),
+        _ => bail!("exactly one side of {} must depend on {}", ROOT, HUMAN),
+    };
+    // inverts the operations from root down to the human
+    while name != HUMAN {
+        let (left, operator, right) = match monkeys.job(name)? {
+            Job::Operation(left, operator, right) => (left.as_str(), *operator, right.as_str()),
+            Job::Number(_) => unreachable!(), // because the monkey depends on the human
+        };
+        if depending.contains(left) && depending.contains(right) {
+            bail!("exactly one side of {} must depend on {}", name, HUMAN);
+        }
+        let inverted = if depending.contains(left) {
+            // target = x op right
+            let right = yell(&yells, right)?;
+            let inverse = match operator {
+                Operator::Add => Operator::Sub,
+                Operator::Sub => Operator::Add,
+                Operator::Mul => Operator::Div,
+                Operator::Div => Operator::Mul,
+            };
+            name = left;
+            target.apply(inverse, &right)
+        } else {
+            // target = left op x
+            let left_value = yell(&yells, left)?;
+            name = right;
+            match operator {
+                Operator::Add => target.apply(Operator::Sub, &left_value),
+                Operator::Sub => left_value.apply(Operator::Sub, &target),
+                Operator::Mul => target.apply(Operator::Div, &left_value),
+                Operator::Div => left_value.apply(Operator::Div, &target),
+            }
+        };
+        target =
+            inverted.ok_or_else(|| anyhow!("cannot solve for {} at monkey {}", HUMAN, name))?;
+    }
+    target.to_integer()
+}
+
+pub struct MonkeyMath;
+
+impl Solver for MonkeyMath {
+    type Input = Monkeys;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_21_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_21_challenge_2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser_utils::{ParseError, ParseErrors};
+    use crate::solve_21::{
+        parse_input, parse_monkey, solve_day_21_challenge_1, solve_day_21_challenge_2, Job,
+        Operator, Rational,
+    };
+
+    const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    #[test]
+    fn test_parse_monkey() {
+        assert_eq!(
+            parse_monkey("pppw: cczh / lfqf"),
+            Ok((
+                "",
+                (
+                    "pppw",
+                    Job::Operation("cczh".to_string(), Operator::Div, "lfqf".to_string())
+                )
+            ))
+        );
+        assert_eq!(parse_monkey("dbpl: 5"), Ok(("", ("dbpl", Job::Number(5)))));
+    }
+
+    #[test]
+    fn test_rational() {
+        let third = Rational::new(2, 6).unwrap();
+        assert_eq!(third, Rational::new(-1, -3).unwrap());
+        assert_eq!(
+            third.apply(Operator::Mul, &Rational::integer(3)),
+            Some(Rational::integer(1))
+        );
+        assert_eq!(
+            Rational::integer(1).apply(Operator::Div, &Rational::integer(-4)),
+            Rational::new(-1, 4)
+        );
+        assert_eq!(third.apply(Operator::Div, &Rational::integer(0)), None);
+    }
+
+    #[test]
+    fn test_solve_day_21_example() {
+        let monkeys = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_21_challenge_1(&monkeys).unwrap(), 152);
+        assert_eq!(solve_day_21_challenge_2(&monkeys).unwrap(), 301);
+    }
+
+    #[test]
+    fn test_parse_input_rejects_invalid_monkeys() {
+        let cycle = parse_input("root: aaaa + bbbb\naaaa: bbbb + bbbb\nbbbb: aaaa * aaaa\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!(cycle.expected, "a monkey that does not wait for itself");
+        let duplicate = parse_input("root: 1\nroot: 2\n")
+            .unwrap_err()
+            .downcast::<ParseErrors>()
+            .unwrap();
+        assert_eq!(duplicate.0[0].line, 2);
+        assert!(parse_input("root: aaaa + zzzz\naaaa: 1\n").is_err());
+    }
+
+    #[test]
+    fn test_human_on_both_sides() {
+        let monkeys =
+            parse_input("root: aaaa + bbbb\naaaa: humn + humn\nbbbb: 36\nhumn: 5\n").unwrap();
+        assert!(solve_day_21_challenge_2(&monkeys).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use crate::grid::{Direction, Grid, Pos, CARDINALS};
+use crate::parser_utils::{parse_all, positive_number, ParseError};
+use crate::solver::{Answer, Solver};
+use anyhow::{anyhow, bail};
+use log::debug;
+use nalgebra::Vector3;
+use nom::branch::alt;
+use nom::character::complete::char;
+use nom::combinator::map;
+use nom::multi::many1;
+use nom::IResult;
+use std::collections::{HashMap, VecDeque};
+
+type Score = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Forward(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+#[derive(Debug)]
+pub struct Notes {
+    board: Grid<Tile>,
+    path: Vec<Instruction>,
+}
+
+fn parse_path(input: &str) -> IResult<&str, Vec<Instruction>> {
+    many1(alt((
+        map(positive_number, Instruction::Forward),
+        map(char('L'), |_| Instruction::TurnLeft),
+        map(char('R'), |_| Instruction::TurnRight),
+    )))(input)
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Notes> {
+    let (board, path) = input
+        .split_once("\n\n")
+        .ok_or_else(|| anyhow!("expected the board and the path separated by a blank line"))?;
+    let width = board.lines().map(|line| line.len()).max().unwrap_or(0);
+    let mut rows = vec![];
+    for line in board.lines() {
+        let mut row = vec![Tile::Void; width];
+        for (col, (index, c)) in line.char_indices().enumerate() {
+            row[col] = match c {
+                ' ' => Tile::Void,
+                '.' => Tile::Open,
+                '#' => Tile::Wall,
+                _ => return Err(ParseError::at(input, &line[index..], "a tile").into()),
+            };
+        }
+        rows.push(row);
+    }
+    let path = parse_all(path, "a number of steps, L or R", parse_path)
+        .map_err(|e| e.within(input, path))?;
+    Ok(Notes {
+        board: Grid::from_rows(rows)?,
+        path,
+    })
+}
+
+fn facing(direction: Direction) -> usize {
+    match direction {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
+    }
+}
+
+// follows the path, next gives the tile in front of the given position and the direction once there
+fn walk(notes: &Notes, next: impl Fn(Pos, Direction) -> (Pos, Direction)) -> anyhow::Result<Score> {
+    let board = &notes.board;
+    let mut pos = board
+        .positions()
+        .find(|p| board[*p] == Tile::Open)
+        .ok_or_else(|| anyhow!("no open tile on the board"))?;
+    let mut direction = Direction::Right;
+    for instruction in notes.path.iter() {
+        match instruction {
+            Instruction::TurnLeft => direction = direction.turn_left(),
+            Instruction::TurnRight => direction = direction.turn_right(),
+            Instruction::Forward(steps) => {
+                for _ in 0..*steps {
+                    let (next_pos, next_direction) = next(pos, direction);
+                    if board[next_pos] == Tile::Wall {
+                        break;
+                    }
+                    (pos, direction) = (next_pos, next_direction);
+                }
+            }
+        }
+    }
+    debug!("final position: {:?} facing {:?}", pos, direction);
+    Ok(1000 * (pos.row + 1) + 4 * (pos.col + 1) + facing(direction))
+}
+
+// wraps around to the other side of the board in the same direction
+fn flat_next(board: &Grid<Tile>, pos: Pos, direction: Direction) -> (Pos, Direction) {
+    let on_board = |p: &Pos| board[*p] != Tile::Void;
+    if let Some(next) = board.step(pos, direction).filter(on_board) {
+        return (next, direction);
+    }
+    let back = board
+        .ray(pos, direction.opposite())
+        .take_while(on_board)
+        .last()
+        .unwrap_or(pos);
+    (back, direction)
+}
+
+// 3d orientation of a face of the cube: its outward normal and where the net's columns and rows go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    origin: Pos,
+    normal: Vector3<i64>,
+    right: Vector3<i64>,
+    down: Vector3<i64>,
+}
+
+impl Face {
+    fn axis(&self, direction: Direction) -> Vector3<i64> {
+        match direction {
+            Direction::Right => self.right,
+            Direction::Left => -self.right,
+            Direction::Down => self.down,
+            Direction::Up => -self.down,
+        }
+    }
+
+    // the neighbouring face of the net, folded over the shared edge
+    fn fold(&self, direction: Direction, origin: Pos) -> Face {
+        let axis = self.axis(direction);
+        let mut face = Face {
+            origin,
+            normal: axis,
+            ..*self
+        };
+        match direction {
+            Direction::Right | Direction::Left => face.right = self.normal * -axis.dot(&self.right),
+            Direction::Down | Direction::Up => face.down = self.normal * -axis.dot(&self.down),
+        }
+        face
+    }
+}
+
+// faces folded from the net, the cube is centered on the origin with an edge of 2 * size
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    fn fold(board: &Grid<Tile>) -> anyhow::Result<Cube> {
+        let tiles = board
+            .iter()
+            .filter(|(_, tile)| **tile != Tile::Void)
+            .count();
+        let size = ((tiles / 6) as f64).sqrt() as usize;
+        if size == 0 || 6 * size * size != tiles {
+            bail!("{} tiles cannot be folded into a cube", tiles);
+        }
+        let face_at = |row: usize, col: usize| {
+            let origin = Pos::new(row * size, col * size);
+            board
+                .get(origin)
+                .filter(|tile| **tile != Tile::Void)
+                .map(|_| origin)
+        };
+        let start = board
+            .positions()
+            .find(|p| board[*p] != Tile::Void)
+            .map(|p| Pos::new(p.row / size * size, p.col / size * size))
+            .expect("a non empty board");
+        let mut faces: HashMap<Pos, Face> = HashMap::new();
+        let mut queue = VecDeque::from([Face {
+            origin: start,
+            normal: Vector3::new(0, 0, -1),
+            right: Vector3::new(1, 0, 0),
+            down: Vector3::new(0, 1, 0),
+        }]);
+        while let Some(face) = queue.pop_front() {
+            if faces.contains_key(&face.origin) {
+                continue;
+            }
+            let (row, col) = (face.origin.row / size, face.origin.col / size);
+            for direction in CARDINALS {
+                let (drow, dcol) = direction.delta();
+                let neighbour = row
+                    .checked_add_signed(drow)
+                    .zip(col.checked_add_signed(dcol))
+                    .and_then(|(r, c)| face_at(r, c));
+                if let Some(origin) = neighbour {
+                    queue.push_back(face.fold(direction, origin));
+                }
+            }
+            faces.insert(face.origin, face);
+        }
+        let mut normals: Vec<_> = faces.values().map(|face| face.normal).collect();
+        normals.sort_by_key(|normal| (normal.x, normal.y, normal.z));
+        normals.dedup();
+        if faces.len() != 6 || normals.len() != 6 {
+            bail!("the board is not the net of a cube");
+        }
+        Ok(Cube {
+            size,
+            faces: faces.into_values().collect(),
+        })
+    }
+
+    fn contains(&self, face: &Face, pos: Pos) -> bool {
+        (face.origin.row..face.origin.row + self.size).contains(&pos.row)
+            && (face.origin.col..face.origin.col + self.size).contains(&pos.col)
+    }
+
+    fn face_of(&self, pos: Pos) -> &Face {
+        self.faces
+            .iter()
+            .find(|face| self.contains(face, pos))
+            .expect("position on a face")
+    }
+
+    // center of the tile in 3d
+    fn point(&self, face: &Face, pos: Pos) -> Vector3<i64> {
+        let size = self.size as i64;
+        let i = (pos.row - face.origin.row) as i64;
+        let j = (pos.col - face.origin.col) as i64;
+        face.normal * size + face.right * (2 * j + 1 - size) + face.down * (2 * i + 1 - size)
+    }
+
+    fn next(&self, board: &Grid<Tile>, pos: Pos, direction: Direction) -> (Pos, Direction) {
+        let face = self.face_of(pos);
+        if let Some(next) = board
+            .step(pos, direction)
+            .filter(|p| self.contains(face, *p))
+        {
+            return (next, direction);
+        }
+        // over the edge onto the face in front, heading away from the face we leave
+        let axis = face.axis(direction);
+        let point = self.point(face, pos) + axis - face.normal;
+        let next_face = self
+            .faces
+            .iter()
+            .find(|f| f.normal == axis)
+            .expect("a face for each normal");
+        let size = self.size as i64;
+        let i = (point.dot(&next_face.down) + size - 1) / 2;
+        let j = (point.dot(&next_face.right) + size - 1) / 2;
+        let next_pos = Pos::new(
+            next_face.origin.row + i as usize,
+            next_face.origin.col + j as usize,
+        );
+        let next_direction = CARDINALS
+            .into_iter()
+            .find(|d| next_face.axis(*d) == -face.normal)
+            .expect("a direction along each axis of the face");
+        (next_pos, next_direction)
+    }
+}
+
+pub fn solve_day_22_challenge_1(notes: &Notes) -> anyhow::Result<Score> {
+    walk(notes, |pos, direction| {
+        flat_next(&notes.board, pos, direction)
+    })
+}
+
+pub fn solve_day_22_challenge_2(notes: &Notes) -> anyhow::Result<Score> {
+    let cube = Cube::fold(&notes.board)?;
+    walk(notes, |pos, direction| {
+        cube.next(&notes.board, pos, direction)
+    })
+}
+
+pub struct MonkeyMap;
+
+impl Solver for MonkeyMap {
+    type Input = Notes;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_22_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_22_challenge_2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Pos, CARDINALS};
+    use crate::solve_22::{
+        parse_input, solve_day_22_challenge_1, solve_day_22_challenge_2, Cube, Tile,
+    };
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+
+    #[test]
+    fn test_solve_day_22_example() {
+        let notes = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_22_challenge_1(&notes).unwrap(), 6032);
+        assert_eq!(solve_day_22_challenge_2(&notes).unwrap(), 5031);
+    }
+
+    #[test]
+    fn test_walk_around_cube() {
+        // a different net of the same cube
+        let board = "  ..\n  ..\n......\n......\n  ..\n  ..\n  ..\n  ..\n\n1\n";
+        for notes in [parse_input(EXAMPLE).unwrap(), parse_input(board).unwrap()] {
+            let cube = Cube::fold(&notes.board).unwrap();
+            let positions: Vec<Pos> = notes
+                .board
+                .iter()
+                .filter(|(_, tile)| **tile != Tile::Void)
+                .map(|(pos, _)| pos)
+                .collect();
+            for pos in positions {
+                for direction in CARDINALS {
+                    let mut state = (pos, direction);
+                    for _ in 0..4 * cube.size {
+                        state = cube.next(&notes.board, state.0, state.1);
+                    }
+                    assert_eq!(state, (pos, direction));
+                }
+            }
+        }
+    }
+}
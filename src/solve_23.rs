@@ -0,0 +1,169 @@
+use crate::parser_utils::parse_grid;
+use crate::solver::{Answer, Solver};
+use log::debug;
+use nalgebra::{Point2, Vector2};
+use std::collections::{HashMap, HashSet};
+
+type Score = usize;
+
+type Elf = Point2<i64>;
+
+// y grows southward
+const NORTH: Vector2<i64> = Vector2::new(0, -1);
+const SOUTH: Vector2<i64> = Vector2::new(0, 1);
+const WEST: Vector2<i64> = Vector2::new(-1, 0);
+const EAST: Vector2<i64> = Vector2::new(1, 0);
+
+// direction of the move, then the three positions that must be free to move there
+const PROPOSALS: [(Vector2<i64>, [Vector2<i64>; 3]); 4] = [
+    (NORTH, [Vector2::new(-1, -1), NORTH, Vector2::new(1, -1)]),
+    (SOUTH, [Vector2::new(-1, 1), SOUTH, Vector2::new(1, 1)]),
+    (WEST, [Vector2::new(-1, -1), WEST, Vector2::new(-1, 1)]),
+    (EAST, [Vector2::new(1, -1), EAST, Vector2::new(1, 1)]),
+];
+
+pub fn parse_input(input: &str) -> anyhow::Result<HashSet<Elf>> {
+    let tiles = parse_grid(input, "an elf or ground", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(tiles
+        .iter()
+        .filter(|(_, elf)| **elf)
+        .map(|(pos, _)| Point2::new(pos.col as i64, pos.row as i64))
+        .collect())
+}
+
+fn has_neighbours(elves: &HashSet<Elf>, elf: &Elf) -> bool {
+    (-1..=1)
+        .flat_map(|dx| (-1..=1).map(move |dy| Vector2::new(dx, dy)))
+        .filter(|delta| *delta != Vector2::zeros())
+        .any(|delta| elves.contains(&(elf + delta)))
+}
+
+fn propose(elves: &HashSet<Elf>, elf: &Elf, round: usize) -> Option<Elf> {
+    if !has_neighbours(elves, elf) {
+        return None;
+    }
+    (0..PROPOSALS.len())
+        .map(|i| &PROPOSALS[(round + i) % PROPOSALS.len()])
+        .find(|(_, checks)| checks.iter().all(|delta| !elves.contains(&(elf + delta))))
+        .map(|(direction, _)| elf + direction)
+}
+
+// the elves after the round and whether any of them moved
+fn spread(elves: &HashSet<Elf>, round: usize) -> (HashSet<Elf>, bool) {
+    let proposals: Vec<(Elf, Option<Elf>)> = elves
+        .iter()
+        .map(|elf| (*elf, propose(elves, elf, round)))
+        .collect();
+    let mut proposed: HashMap<Elf, usize> = HashMap::new();
+    for target in proposals.iter().filter_map(|(_, target)| *target) {
+        *proposed.entry(target).or_insert(0) += 1;
+    }
+    let mut moved = false;
+    let spread = proposals
+        .into_iter()
+        .map(|(elf, target)| match target {
+            // elves proposing the same position stay put
+            Some(target) if proposed[&target] == 1 => {
+                moved = true;
+                target
+            }
+            _ => elf,
+        })
+        .collect();
+    (spread, moved)
+}
+
+fn empty_ground(elves: &HashSet<Elf>) -> usize {
+    let (min_x, max_x) = elves
+        .iter()
+        .map(|elf| elf.x)
+        .fold((i64::MAX, i64::MIN), |(min, max), x| {
+            (min.min(x), max.max(x))
+        });
+    let (min_y, max_y) = elves
+        .iter()
+        .map(|elf| elf.y)
+        .fold((i64::MAX, i64::MIN), |(min, max), y| {
+            (min.min(y), max.max(y))
+        });
+    ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize - elves.len()
+}
+
+pub fn solve_day_23_challenge_1(elves: &HashSet<Elf>) -> Score {
+    if elves.is_empty() {
+        return 0;
+    }
+    let mut elves = elves.clone();
+    for round in 0..10 {
+        elves = spread(&elves, round).0;
+    }
+    empty_ground(&elves)
+}
+
+pub fn solve_day_23_challenge_2(elves: &HashSet<Elf>) -> Score {
+    let mut elves = elves.clone();
+    let mut round = 0;
+    loop {
+        let (spread, moved) = spread(&elves, round);
+        round += 1;
+        if !moved {
+            debug!("{} elves settled", spread.len());
+            return round;
+        }
+        elves = spread;
+    }
+}
+
+pub struct UnstableDiffusion;
+
+impl Solver for UnstableDiffusion {
+    type Input = HashSet<Elf>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_23_challenge_1(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_23_challenge_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_23::{
+        parse_input, solve_day_23_challenge_1, solve_day_23_challenge_2, spread,
+    };
+
+    const EXAMPLE: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..
+";
+
+    #[test]
+    fn test_spread_small_example() {
+        let elves = parse_input(".....\n..##.\n..#..\n.....\n..##.\n.....\n").unwrap();
+        let expected = parse_input("..##.\n.....\n..#..\n...#.\n..#..\n.....\n").unwrap();
+        let (elves, moved) = spread(&elves, 0);
+        assert!(moved);
+        assert_eq!(elves, expected);
+    }
+
+    #[test]
+    fn test_solve_day_23_example() {
+        let elves = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_23_challenge_1(&elves), 110);
+        assert_eq!(solve_day_23_challenge_2(&elves), 20);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::grid::{Direction, Grid, Pos};
+use crate::math_utils::lcm;
+use crate::parser_utils::{parse_grid, ParseError, ParseErrors};
+use crate::solver::{Answer, Solver};
+use anyhow::{anyhow, bail};
+use log::debug;
+use pathfinding::prelude::bfs;
+
+type Score = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Wall,
+    Ground,
+    Blizzard(Direction),
+}
+
+// blizzards as they are at minute 0, the entrance and the exit are in the top and bottom walls
+#[derive(Debug)]
+pub struct Valley {
+    tiles: Grid<Tile>,
+    entrance: Pos,
+    exit: Pos,
+    // blizzards are back to their initial positions after that many minutes
+    period: usize,
+}
+
+// the rest of the input line from the tile at pos
+fn tile_at(input: &str, pos: Pos) -> &str {
+    let line = input.lines().nth(pos.row).unwrap_or("");
+    line.char_indices()
+        .nth(pos.col)
+        .map_or(&line[line.len()..], |(index, _)| &line[index..])
+}
+
+// the only ground tile of a top or bottom row
+fn gap(input: &str, tiles: &Grid<Tile>, row: usize) -> Result<Pos, ParseError> {
+    let gaps: Vec<Pos> = (0..tiles.ncols())
+        .map(|col| Pos::new(row, col))
+        .filter(|pos| tiles[*pos] == Tile::Ground)
+        .collect();
+    match gaps.as_slice() {
+        [gap] => Ok(*gap),
+        [] => Err(ParseError::at(
+            input,
+            tile_at(input, Pos::new(row, 0)),
+            "a wall with a gap",
+        )),
+        [_, extra, ..] => Err(ParseError::at(input, tile_at(input, *extra), "a wall")),
+    }
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Valley> {
+    let tiles = parse_grid(input, "a wall, ground or a blizzard", |c| match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Ground),
+        '^' => Some(Tile::Blizzard(Direction::Up)),
+        'v' => Some(Tile::Blizzard(Direction::Down)),
+        '<' => Some(Tile::Blizzard(Direction::Left)),
+        '>' => Some(Tile::Blizzard(Direction::Right)),
+        _ => None,
+    })?;
+    if tiles.nrows() < 3 || tiles.ncols() < 3 {
+        bail!("the valley is too small");
+    }
+    let (last_row, last_col) = (tiles.nrows() - 1, tiles.ncols() - 1);
+    // walls all around, with gaps in the top and bottom rows only
+    let errors: Vec<ParseError> = tiles
+        .iter()
+        .filter(|(pos, tile)| match tile {
+            Tile::Wall => false,
+            Tile::Ground => pos.col == 0 || pos.col == last_col,
+            Tile::Blizzard(_) => {
+                pos.row == 0 || pos.row == last_row || pos.col == 0 || pos.col == last_col
+            }
+        })
+        .map(|(pos, _)| ParseError::at(input, tile_at(input, pos), "a wall"))
+        .collect();
+    if !errors.is_empty() {
+        return Err(ParseErrors(errors).into());
+    }
+    let entrance = gap(input, &tiles, 0)?;
+    let exit = gap(input, &tiles, last_row)?;
+    let period = lcm(tiles.nrows() as i64 - 2, tiles.ncols() as i64 - 2)
+        .ok_or_else(|| anyhow!("the valley is too large"))? as usize;
+    Ok(Valley {
+        tiles,
+        entrance,
+        exit,
+        period,
+    })
+}
+
+impl Valley {
+    // no wall and no blizzard at that minute
+    fn is_free(&self, pos: Pos, minute: usize) -> bool {
+        match self.tiles.get(pos) {
+            None | Some(Tile::Wall) => return false,
+            _ => {}
+        }
+        if pos == self.entrance || pos == self.exit {
+            return true;
+        }
+        // blizzards wrap around inside the walls
+        let (height, width) = (self.tiles.nrows() - 2, self.tiles.ncols() - 2);
+        let (row, col) = (pos.row - 1, pos.col - 1);
+        let (rows_moved, cols_moved) = (minute % height, minute % width);
+        let blizzard_from = |row: usize, col: usize, direction: Direction| {
+            self.tiles[Pos::new(row + 1, col + 1)] == Tile::Blizzard(direction)
+        };
+        !(blizzard_from(row, (col + width - cols_moved) % width, Direction::Right)
+            || blizzard_from(row, (col + cols_moved) % width, Direction::Left)
+            || blizzard_from((row + height - rows_moved) % height, col, Direction::Down)
+            || blizzard_from((row + rows_moved) % height, col, Direction::Up))
+    }
+
+    // minutes to go from start to goal when leaving at the given minute
+    fn crossing(&self, start: Pos, goal: Pos, minute: usize) -> anyhow::Result<usize> {
+        // minutes are taken modulo the period so that the search space is finite
+        let path = bfs(
+            &(start, minute % self.period),
+            |(pos, minute)| {
+                let next = (minute + 1) % self.period;
+                self.tiles
+                    .neighbours4(*pos)
+                    .chain(std::iter::once(*pos))
+                    .filter(|p| self.is_free(*p, next))
+                    .map(|p| (p, next))
+                    .collect::<Vec<(Pos, usize)>>()
+            },
+            |(pos, _)| *pos == goal,
+        )
+        .ok_or_else(|| anyhow!("no way from {:?} to {:?}", start, goal))?;
+        Ok(path.len() - 1)
+    }
+}
+
+pub fn solve_day_24_challenge_1(valley: &Valley) -> anyhow::Result<Score> {
+    valley.crossing(valley.entrance, valley.exit, 0)
+}
+
+pub fn solve_day_24_challenge_2(valley: &Valley) -> anyhow::Result<Score> {
+    let mut minute = 0;
+    for (start, goal) in [
+        (valley.entrance, valley.exit),
+        (valley.exit, valley.entrance),
+        (valley.entrance, valley.exit),
+    ] {
+        minute += valley.crossing(start, goal, minute)?;
+        debug!("reached {:?} at minute {}", goal, minute);
+    }
+    Ok(minute)
+}
+
+pub struct BlizzardBasin;
+
+impl Solver for BlizzardBasin {
+    type Input = Valley;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_24_challenge_1(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_24_challenge_2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Pos;
+    use crate::parser_utils::{ParseError, ParseErrors};
+    use crate::solve_24::{parse_input, solve_day_24_challenge_1, solve_day_24_challenge_2};
+
+    const EXAMPLE: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    #[test]
+    fn test_blizzards() {
+        let valley =
+            parse_input("#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#\n").unwrap();
+        assert_eq!(valley.period, 5);
+        assert!(!valley.is_free(Pos::new(2, 1), 0));
+        assert!(!valley.is_free(Pos::new(2, 4), 3));
+        assert!(!valley.is_free(Pos::new(2, 1), 5)); // wrapped around
+        assert!(!valley.is_free(Pos::new(1, 4), 2));
+        assert!(valley.is_free(Pos::new(4, 4), 2));
+        assert!(!valley.is_free(Pos::new(0, 2), 0)); // wall
+    }
+
+    #[test]
+    fn test_solve_day_24_example() {
+        let valley = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_day_24_challenge_1(&valley).unwrap(), 18);
+        assert_eq!(solve_day_24_challenge_2(&valley).unwrap(), 54);
+    }
+
+    #[test]
+    fn test_border_must_be_walls() {
+        let errors = parse_input("#.###\n.....\n###.#\n")
+            .unwrap_err()
+            .downcast::<ParseErrors>()
+            .unwrap();
+        assert_eq!((errors.0[0].line, errors.0[0].column), (2, 1));
+        assert_eq!((errors.0[1].line, errors.0[1].column), (2, 5));
+        let error = parse_input("#..##\n#...#\n###.#\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::parser_utils::parse_lines;
+use crate::solver::{Answer, Solver};
+use anyhow::{anyhow, bail};
+use nom::bytes::complete::is_a;
+use nom::combinator::map_res;
+use nom::IResult;
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+use std::str::FromStr;
+
+// balanced base 5 with digits = (-2), - (-1), 0, 1 and 2
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snafu(i64);
+
+impl FromStr for Snafu {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            bail!("empty SNAFU number");
+        }
+        // i128 so that the last digit can bring a value past i64::MAX or i64::MIN back in range
+        let mut value: i128 = 0;
+        for c in s.chars() {
+            let digit = match c {
+                '=' => -2,
+                '-' => -1,
+                '0' => 0,
+                '1' => 1,
+                '2' => 2,
+                _ => bail!("unexpected SNAFU digit {:?} in {}", c, s),
+            };
+            value = value
+                .checked_mul(5)
+                .and_then(|v| v.checked_add(digit))
+                .ok_or_else(|| anyhow!("{} does not fit in an i64", s))?;
+        }
+        let value = i64::try_from(value).map_err(|_| anyhow!("{} does not fit in an i64", s))?;
+        Ok(Snafu(value))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // i128 so that carrying past i64::MAX or i64::MIN does not overflow
+        let mut value = self.0 as i128;
+        let mut digits = vec![];
+        loop {
+            let digit = (value + 2).rem_euclid(5) - 2;
+            digits.push(match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            });
+            value = (value - digit) / 5;
+            if value == 0 {
+                break;
+            }
+        }
+        write!(f, "{}", digits.iter().rev().collect::<String>())
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Snafu(value)
+    }
+}
+
+impl From<Snafu> for i64 {
+    fn from(snafu: Snafu) -> Self {
+        snafu.0
+    }
+}
+
+impl Snafu {
+    // none when the sum does not fit in an i64
+    pub fn checked_add(self, other: Snafu) -> Option<Snafu> {
+        self.0.checked_add(other.0).map(Snafu)
+    }
+}
+
+// panics on overflow, use checked_add when the sum may not fit in an i64
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        self.checked_add(other).expect("SNAFU addition overflow")
+    }
+}
+
+fn parse_snafu(input: &str) -> IResult<&str, Snafu> {
+    map_res(is_a("=-012"), Snafu::from_str)(input)
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Snafu>> {
+    Ok(parse_lines(input, "a SNAFU number", parse_snafu)?)
+}
+
+pub fn solve_day_25_challenge_1(numbers: &[Snafu]) -> anyhow::Result<Snafu> {
+    numbers.iter().try_fold(Snafu(0), |sum, snafu| {
+        sum.checked_add(*snafu)
+            .ok_or_else(|| anyhow!("the sum of the SNAFU numbers does not fit in an i64"))
+    })
+}
+
+pub struct FullOfHotAir;
+
+impl Solver for FullOfHotAir {
+    type Input = Vec<Snafu>;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(solve_day_25_challenge_1(input)?.to_string().into())
+    }
+
+    // the last day has a single challenge
+    fn part_two(&self, _input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(Answer::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_25::{parse_input, solve_day_25_challenge_1, Snafu};
+    use std::str::FromStr;
+
+    const EXAMPLE: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122
+";
+
+    #[test]
+    fn test_snafu() {
+        for (decimal, snafu) in [
+            (1, "1"),
+            (3, "1="),
+            (8, "2="),
+            (10, "20"),
+            (15, "1=0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-1, "-"),
+            (-3, "-2"),
+            (-8, "=2"),
+        ] {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(i64::from(Snafu::from_str(snafu).unwrap()), decimal);
+        }
+        assert!(Snafu::from_str("").is_err());
+        assert!(Snafu::from_str("1=3").is_err());
+        assert!(Snafu::from_str("2222222222222222222222222222").is_err());
+    }
+
+    #[test]
+    fn test_snafu_round_trip() {
+        let small = -100_000..=100_000;
+        // large steps spanning the whole i64 range
+        let large = (-5_000..=5_000).map(|i: i64| i * (i64::MAX / 5_000) + i % 7);
+        for decimal in small.chain(large).chain([i64::MIN, i64::MAX]) {
+            let snafu = Snafu::from(decimal);
+            assert_eq!(Snafu::from_str(&snafu.to_string()).unwrap(), snafu);
+        }
+    }
+
+    #[test]
+    fn test_snafu_addition() {
+        for (a, b) in [(1, 2), (-7, 30), (2022, -2022), (976, 12345)] {
+            assert_eq!(
+                (Snafu::from(a) + Snafu::from(b)).to_string(),
+                Snafu::from(a + b).to_string()
+            );
+        }
+        assert_eq!(Snafu::from(i64::MAX).checked_add(Snafu::from(1)), None);
+    }
+
+    #[test]
+    fn test_solve_day_25_example() {
+        let numbers = parse_input(EXAMPLE).unwrap();
+        assert_eq!(i64::from(solve_day_25_challenge_1(&numbers).unwrap()), 4890);
+        assert_eq!(
+            solve_day_25_challenge_1(&numbers).unwrap().to_string(),
+            "2=-1=0"
+        );
+        // each number fits in an i64 but not their sum
+        let numbers = parse_input(&"222222222222222222222222222\n".repeat(3)).unwrap();
+        assert!(solve_day_25_challenge_1(&numbers).is_err());
+    }
+}
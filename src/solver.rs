@@ -0,0 +1,129 @@
+use anyhow::bail;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    NotFound,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::NotFound => write!(f, "not found"),
+        }
+    }
+}
+
+// answers that were not found are null
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(n) => serializer.serialize_i64(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::NotFound => serializer.serialize_none(),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::NotFound, |v| v.into())
+    }
+}
+
+pub trait Solver {
+    type Input;
+
+    fn parse_input(&self, input: &str) -> anyhow::Result<Self::Input>;
+    fn part_one(&self, input: &Self::Input) -> anyhow::Result<Answer>;
+    fn part_two(&self, input: &Self::Input) -> anyhow::Result<Answer>;
+}
+
+// object safe counterpart of `Solver` so that days with different inputs can share a registry
+pub trait Puzzle {
+    fn parse<'a>(&'a self, input: &str) -> anyhow::Result<Box<dyn ParsedPuzzle + 'a>>;
+}
+
+pub trait ParsedPuzzle {
+    fn solve(&self, challenge: usize) -> anyhow::Result<Answer>;
+}
+
+struct Parsed<'a, S: Solver> {
+    solver: &'a S,
+    input: S::Input,
+}
+
+impl<'a, S: Solver> ParsedPuzzle for Parsed<'a, S> {
+    fn solve(&self, challenge: usize) -> anyhow::Result<Answer> {
+        match challenge {
+            1 => self.solver.part_one(&self.input),
+            2 => self.solver.part_two(&self.input),
+            _ => bail!("no challenge {}, expected 1 or 2", challenge),
+        }
+    }
+}
+
+impl<S: Solver> Puzzle for S {
+    fn parse<'a>(&'a self, input: &str) -> anyhow::Result<Box<dyn ParsedPuzzle + 'a>> {
+        let input = self.parse_input(input)?;
+        Ok(Box::new(Parsed {
+            solver: self,
+            input,
+        }))
+    }
+}
+
+pub const CHALLENGES: [usize; 2] = [1, 2];
+
+#[derive(Default)]
+pub struct Registry {
+    puzzles: BTreeMap<usize, Box<dyn Puzzle>>,
+}
+
+impl Registry {
+    pub fn register<S: Solver + 'static>(&mut self, day: usize, solver: S) {
+        self.puzzles.insert(day, Box::new(solver));
+    }
+
+    pub fn get(&self, day: usize) -> Option<&dyn Puzzle> {
+        self.puzzles.get(&day).map(|puzzle| puzzle.as_ref())
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = usize> + '_ {
+        self.puzzles.keys().copied()
+    }
+}
+
+pub fn input_file_name(day: usize) -> String {
+    format!("{:02}.txt", day)
+}
@@ -0,0 +1,169 @@
+use crate::input::InputSource;
+use crate::runner::run_day;
+use anyhow::bail;
+use aoc_2022::solver::{input_file_name, Answer, Registry};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ExpectedAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl ExpectedAnswer {
+    fn matches(&self, answer: &Answer) -> bool {
+        match (self, answer) {
+            (ExpectedAnswer::Number(expected), Answer::Number(actual)) => expected == actual,
+            (ExpectedAnswer::Text(expected), Answer::Text(actual)) => expected == actual,
+            _ => false,
+        }
+    }
+}
+
+impl Display for ExpectedAnswer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedAnswer::Number(n) => write!(f, "{}", n),
+            ExpectedAnswer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Expected {
+    pub day: usize,
+    pub challenge: usize,
+    pub input: Option<String>, // NN.txt when omitted
+    pub answer: ExpectedAnswer,
+}
+
+impl Expected {
+    fn input_file_name(&self) -> String {
+        self.input
+            .clone()
+            .unwrap_or_else(|| input_file_name(self.day))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    answer: Vec<Expected>,
+}
+
+pub(crate) fn parse_manifest(input: &str) -> anyhow::Result<Vec<Expected>> {
+    let manifest: Manifest = toml::from_str(input)?;
+    Ok(manifest.answer)
+}
+
+pub(crate) fn load_manifest(manifest_path: &Path) -> anyhow::Result<Vec<Expected>> {
+    let content = fs::read_to_string(manifest_path)?;
+    parse_manifest(content.as_str())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Mismatch,
+    Failed,
+    NoSolver,
+}
+
+// keeps multi-line answers such as the day 10 CRT image on a single table row
+fn single_line(answer: &str) -> String {
+    answer.replace('\n', "\\n")
+}
+
+// runs every expected answer, inputs shared by several challenges are parsed once
+pub(crate) fn verify(
+    registry: &Registry,
+    expected: &[Expected],
+    data_path: &Path,
+) -> anyhow::Result<()> {
+    let mut by_input: BTreeMap<(usize, String), Vec<&Expected>> = BTreeMap::new();
+    for e in expected {
+        by_input
+            .entry((e.day, e.input_file_name()))
+            .or_default()
+            .push(e);
+    }
+
+    println!(
+        "{:>3}  {:>9}  {:<16}  {:<9}  {:<20}  actual",
+        "day", "challenge", "input", "status", "expected"
+    );
+    let mut failed = 0;
+    for ((day, input), expected) in by_input {
+        let challenges: Vec<usize> = expected.iter().map(|e| e.challenge).collect();
+        let runs = match registry.get(day) {
+            Some(puzzle) => {
+                let input_source = InputSource::File(data_path.join(&input));
+                run_day(puzzle, day, &challenges, &input_source)
+            }
+            None => vec![],
+        };
+        for e in expected {
+            let run = runs.iter().find(|run| run.challenge == e.challenge);
+            let (status, actual) = match run.map(|run| &run.answer) {
+                None => (Status::NoSolver, String::new()),
+                Some(Ok(answer)) if e.answer.matches(answer) => (Status::Ok, answer.to_string()),
+                Some(Ok(answer)) => (Status::Mismatch, answer.to_string()),
+                Some(Err(error)) => (Status::Failed, format!("{:#}", error)),
+            };
+            if status != Status::Ok {
+                failed += 1;
+            }
+            println!(
+                "{:>3}  {:>9}  {:<16}  {:<9}  {:<20}  {}",
+                day,
+                e.challenge,
+                input,
+                format!("{:?}", status),
+                single_line(&e.answer.to_string()),
+                single_line(&actual)
+            );
+        }
+    }
+    if failed > 0 {
+        bail!(
+            "{} out of {} answers did not verify",
+            failed,
+            expected.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::verify::{parse_manifest, Expected, ExpectedAnswer};
+
+    #[test]
+    fn test_parse_manifest() {
+        assert_eq!(
+            parse_manifest(
+                "[[answer]]\nday = 5\nchallenge = 1\nanswer = \"CMZ\"\n\n[[answer]]\nday = 8\nchallenge = 2\ninput = \"08_test.txt\"\nanswer = 8\n"
+            )
+            .unwrap(),
+            vec![
+                Expected {
+                    day: 5,
+                    challenge: 1,
+                    input: None,
+                    answer: ExpectedAnswer::Text(String::from("CMZ")),
+                },
+                Expected {
+                    day: 8,
+                    challenge: 2,
+                    input: Some(String::from("08_test.txt")),
+                    answer: ExpectedAnswer::Number(8),
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use aoc_2022::grid::Pos;
+use aoc_2022::parser_utils::parse_grid;
+use aoc_2022::solver::{Answer, Solver, CHALLENGES};
+use aoc_2022::{registry, solve_07, solve_09, solve_12, solve_13, solve_25};
+use std::fs;
+
+#[test]
+fn test_solve_functions() {
+    let terminal_lines =
+        solve_07::parse_input(&fs::read_to_string("data/07.txt").unwrap()).unwrap();
+    assert_eq!(solve_07::solve_day_7_challenge_1(&terminal_lines), 1232307);
+    assert_eq!(
+        solve_07::solve_day_7_challenge_2(&terminal_lines),
+        Some(7268994)
+    );
+
+    let moves = solve_09::parse_input("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n").unwrap();
+    assert_eq!(solve_09::solve_day_9_challenge_1(&moves), 13);
+    assert_eq!(solve_09::solve_day_9_challenge_2(&moves), 1);
+}
+
+#[test]
+fn test_solver_trait() {
+    let input = fs::read_to_string("data/12_test.txt").unwrap();
+    let solver = solve_12::HillClimbingAlgorithm;
+    let heightmap = solver.parse_input(&input).unwrap();
+    assert_eq!(solver.part_one(&heightmap).unwrap(), Answer::Number(31));
+    assert_eq!(solver.part_two(&heightmap).unwrap(), Answer::Number(29));
+}
+
+#[test]
+fn test_registry() {
+    let registry = registry();
+    assert_eq!(
+        registry.days().collect::<Vec<usize>>(),
+        (1..=25).collect::<Vec<usize>>()
+    );
+    let puzzle = registry.get(25).unwrap();
+    let parsed = puzzle.parse("1=-0-2\n12111\n2=0=\n").unwrap();
+    assert_eq!(parsed.solve(1).unwrap(), Answer::Text("10=-01".to_string()));
+    assert!(parsed.solve(3).is_err());
+    assert!(puzzle.parse("not a snafu number\n").is_err());
+    for challenge in CHALLENGES {
+        assert!(registry
+            .get(13)
+            .unwrap()
+            .parse("[1]\n[2]\n")
+            .unwrap()
+            .solve(challenge)
+            .is_ok());
+    }
+}
+
+#[test]
+fn test_public_types() {
+    let packets = solve_13::parse_input("[[1],4]\n[[1],[2,3,4]]\n").unwrap();
+    let (left, right) = &packets[0];
+    assert!(left > right);
+    assert_eq!(left.to_string(), "[[1],4]");
+
+    let snafu: solve_25::Snafu = "2=-01".parse().unwrap();
+    assert_eq!(i64::from(snafu), 976);
+    assert_eq!((snafu + solve_25::Snafu::from(24)).to_string(), "2=000");
+
+    let grid = parse_grid("ab\ncd\n", "any character", Some).unwrap();
+    assert_eq!(grid[Pos::new(1, 0)], 'c');
+}